@@ -37,7 +37,7 @@ impl Component for App {
                         sender.oneshot_command(async move {
                             tokio::time::sleep(Duration::from_secs(1)).await;
                             Msg::Increment
-                        })
+                        });
                     },
                 },
 
@@ -46,7 +46,7 @@ impl Component for App {
                         sender.oneshot_command(async move {
                             tokio::time::sleep(Duration::from_secs(1)).await;
                             Msg::Decrement
-                        })
+                        });
                     },
                 },
 
@@ -38,7 +38,7 @@ impl Component for App {
                         sender.spawn_oneshot_command(|| {
                             std::thread::sleep(Duration::from_secs(1));
                             Msg::Increment
-                        })
+                        });
                     },
                 },
 
@@ -47,7 +47,7 @@ impl Component for App {
                         sender.spawn_oneshot_command(|| {
                             std::thread::sleep(Duration::from_secs(1));
                             Msg::Decrement
-                        })
+                        });
                     },
                 },
 
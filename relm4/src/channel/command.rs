@@ -0,0 +1,377 @@
+use std::collections::hash_map::DefaultHasher;
+use std::collections::HashMap;
+use std::fmt;
+use std::future::Future;
+use std::hash::{Hash, Hasher};
+use std::pin::Pin;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex};
+use std::task::{Context, Poll};
+
+use flume::r#async::RecvStream;
+use futures::future::{AbortHandle, AbortRegistration, Abortable, FusedFuture};
+use futures::{task, Stream};
+
+use super::{Receiver, Sender};
+use crate::GuardedReceiver;
+
+/// Cancels a command spawned by a component or factory.
+///
+/// Dropping the handle doesn't cancel the command.
+#[derive(Debug, Clone)]
+pub struct CommandHandle {
+    abort: AbortHandle,
+    finished: Arc<AtomicBool>,
+}
+
+impl CommandHandle {
+    pub(super) fn new() -> (Self, AbortRegistration) {
+        let (abort, registration) = AbortHandle::new_pair();
+        let handle = Self {
+            abort,
+            finished: Arc::default(),
+        };
+        (handle, registration)
+    }
+
+    /// Aborts the command.
+    ///
+    /// Outputs of the command that weren't processed by the component yet are discarded,
+    /// even if they were sent before the command was aborted.
+    ///
+    /// Asynchronous commands are dropped the next time they yield.
+    /// Synchronous commands can't be interrupted once they run,
+    /// but all their outputs are discarded as well.
+    pub fn abort(&self) {
+        self.abort.abort();
+    }
+
+    /// Returns [`true`] if [`abort()`](Self::abort) was called.
+    ///
+    /// This doesn't mean that the command stopped already, see [`is_finished()`](Self::is_finished).
+    #[must_use]
+    pub fn is_aborted(&self) -> bool {
+        self.abort.is_aborted()
+    }
+
+    /// Returns [`true`] once the command doesn't run anymore.
+    ///
+    /// This is the case if the command returned, if it was dropped after it was aborted
+    /// or the component shut down, or if it was aborted before it started.
+    /// Use [`is_aborted()`](Self::is_aborted) to tell whether the command was aborted.
+    #[must_use]
+    pub fn is_finished(&self) -> bool {
+        self.finished.load(Ordering::Acquire)
+    }
+
+    /// Marks the command as finished.
+    pub(super) fn finish(&self) {
+        self.finished.store(true, Ordering::Release);
+    }
+
+    /// Wraps the future of a command, so it can be aborted.
+    pub(super) fn wrap<F: Future<Output = ()>>(
+        &self,
+        registration: AbortRegistration,
+        future: F,
+    ) -> impl Future<Output = ()> {
+        let handle = self.clone();
+        async move {
+            Abortable::new(future, registration).await.ok();
+            handle.finish();
+        }
+    }
+}
+
+/// Creates the channel for the outputs of the commands of a component.
+pub(crate) fn command_channel<T>() -> (CommandSender<T>, CommandReceiver<T>) {
+    let (untagged_sender, untagged_receiver) = crate::channel();
+    let (commands_sender, commands_receiver) = crate::channel();
+    let sender = CommandSender {
+        untagged: untagged_sender,
+        commands: commands_sender,
+    };
+    let receiver = CommandReceiver {
+        untagged: GuardedReceiver::new(untagged_receiver),
+        registered: GuardedReceiver::new(commands_receiver),
+        commands: Vec::new(),
+    };
+    (sender, receiver)
+}
+
+/// Sends command outputs to the runtime of a component.
+pub(crate) struct CommandSender<T> {
+    // Outputs that don't belong to a command, for example from the command sender of a component.
+    untagged: Sender<T>,
+    commands: Sender<(CommandHandle, Receiver<T>)>,
+}
+
+impl<T> CommandSender<T> {
+    /// Returns the sender for outputs that don't belong to a command.
+    pub(crate) const fn untagged(&self) -> &Sender<T> {
+        &self.untagged
+    }
+
+    /// Returns a sender for the outputs of a new command.
+    ///
+    /// The runtime discards its outputs once the command is aborted.
+    pub(crate) fn register(&self, handle: &CommandHandle) -> Sender<T> {
+        let (sender, receiver) = crate::channel();
+        self.commands.send((handle.clone(), receiver)).ok();
+        sender
+    }
+}
+
+impl<T> Clone for CommandSender<T> {
+    fn clone(&self) -> Self {
+        Self {
+            untagged: self.untagged.clone(),
+            commands: self.commands.clone(),
+        }
+    }
+}
+
+impl<T> fmt::Debug for CommandSender<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("CommandSender")
+            .field("untagged", &self.untagged)
+            .finish_non_exhaustive()
+    }
+}
+
+/// Receives the outputs of all commands of a component, except for aborted commands.
+pub(crate) struct CommandReceiver<T: 'static> {
+    untagged: GuardedReceiver<'static, T>,
+    registered: GuardedReceiver<'static, (CommandHandle, Receiver<T>)>,
+    commands: Vec<(CommandHandle, RecvStream<'static, T>)>,
+}
+
+impl<T> CommandReceiver<T> {
+    /// Returns the next output and the handle of its command, if it belongs to one.
+    fn poll_tagged(&mut self, cx: &mut Context<'_>) -> Poll<(Option<CommandHandle>, T)> {
+        if let Poll::Ready(output) = Pin::new(&mut self.untagged).poll(cx) {
+            return Poll::Ready((None, output));
+        }
+        while let Poll::Ready((handle, receiver)) = Pin::new(&mut self.registered).poll(cx) {
+            self.commands.push((handle, receiver.into_stream()));
+        }
+
+        let mut index = 0;
+        while let Some((handle, outputs)) = self.commands.get_mut(index) {
+            // Drops the outputs that weren't processed yet.
+            if handle.is_aborted() {
+                self.commands.swap_remove(index);
+                continue;
+            }
+            match Pin::new(outputs).poll_next(cx) {
+                Poll::Ready(Some(output)) => return Poll::Ready((Some(handle.clone()), output)),
+                Poll::Ready(None) => {
+                    self.commands.swap_remove(index);
+                }
+                Poll::Pending => index += 1,
+            }
+        }
+        Poll::Pending
+    }
+
+    /// Takes all outputs that were received so far together with the handles of their commands.
+    pub(crate) fn drain_tagged(&mut self) -> Vec<(Option<CommandHandle>, T)> {
+        let mut cx = Context::from_waker(task::noop_waker_ref());
+        let mut outputs = Vec::new();
+        while let Poll::Ready(output) = self.poll_tagged(&mut cx) {
+            outputs.push(output);
+        }
+        outputs
+    }
+}
+
+impl<T> Future for CommandReceiver<T> {
+    type Output = T;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        self.get_mut().poll_tagged(cx).map(|(_, output)| output)
+    }
+}
+
+impl<T> FusedFuture for CommandReceiver<T> {
+    fn is_terminated(&self) -> bool {
+        self.untagged.is_terminated() && self.registered.is_terminated() && self.commands.is_empty()
+    }
+}
+
+impl<T> fmt::Debug for CommandReceiver<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("CommandReceiver")
+            .field("commands", &self.commands.len())
+            .finish_non_exhaustive()
+    }
+}
+
+/// Commands that were started with a key.
+#[derive(Debug, Default)]
+pub(super) struct KeyedCommands(Mutex<HashMap<u64, CommandHandle>>);
+
+impl KeyedCommands {
+    /// Stores the handle of a new command and aborts the previous command with the same key.
+    pub(super) fn replace(&self, key: impl Hash, handle: &CommandHandle) {
+        let mut hasher = DefaultHasher::new();
+        key.hash(&mut hasher);
+
+        let mut commands = self.0.lock().unwrap();
+        // Finished commands don't need to be tracked anymore.
+        commands.retain(|_, handle| !handle.is_finished());
+        if let Some(previous) = commands.insert(hasher.finish(), handle.clone()) {
+            previous.abort();
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use gtk::glib;
+
+    use super::CommandHandle;
+    use crate::testing::{ComponentTester, ManualExecutor};
+    use crate::{Component, ComponentController, ComponentParts, ComponentSender};
+
+    #[derive(Debug)]
+    enum Msg {
+        Search(u8),
+        Compute(u8),
+        Import,
+        Cancel,
+    }
+
+    #[derive(Default)]
+    struct Search {
+        handle: Option<CommandHandle>,
+        results: Vec<u8>,
+    }
+
+    impl Component for Search {
+        type CommandOutput = u8;
+        type Input = Msg;
+        type Output = ();
+        type Init = ();
+        type Root = ();
+        type Widgets = ();
+
+        fn init_root() -> Self::Root {}
+
+        fn init(
+            _init: Self::Init,
+            _root: Self::Root,
+            _sender: ComponentSender<Self>,
+        ) -> ComponentParts<Self> {
+            ComponentParts {
+                model: Search::default(),
+                widgets: (),
+            }
+        }
+
+        fn update(&mut self, message: Self::Input, sender: ComponentSender<Self>, _: &()) {
+            match message {
+                Msg::Search(value) => {
+                    sender.keyed_oneshot_command("search", async move { value });
+                }
+                Msg::Compute(value) => {
+                    self.handle = Some(sender.spawn_oneshot_command(move || value));
+                }
+                Msg::Import => {
+                    sender.stream_command(futures::stream::iter([4, 5, 6]));
+                }
+                Msg::Cancel => self.handle.take().unwrap().abort(),
+            }
+        }
+
+        fn update_cmd(&mut self, message: Self::CommandOutput, _: ComponentSender<Self>, _: &()) {
+            self.results.push(message);
+        }
+    }
+
+    #[gtk::test]
+    fn abort_commands() {
+        let executor = ManualExecutor::new();
+        let builder = Search::builder().command_executor(executor.clone());
+        let mut tester = ComponentTester::from_builder(builder, ());
+
+        // Only the latest search finishes.
+        tester.send(Msg::Search(1));
+        tester.send(Msg::Search(2));
+        executor.run_all();
+        tester.process();
+        assert_eq!(tester.command_outputs(), &[2]);
+
+        tester.send(Msg::Compute(3));
+        tester.send(Msg::Cancel);
+        executor.run_all();
+        tester.process();
+        assert_eq!(tester.command_outputs(), &[2]);
+
+        // Captured outputs are dropped if their command is aborted before they're delivered.
+        tester.send(Msg::Compute(4));
+        executor.run_all();
+        tester.process();
+        assert_eq!(tester.command_outputs(), &[2, 4]);
+        tester.send(Msg::Cancel);
+        assert_eq!(tester.command_outputs(), &[2]);
+    }
+
+    #[gtk::test]
+    fn discard_outputs_sent_before_abort() {
+        let executor = ManualExecutor::new();
+        let controller = Search::builder()
+            .command_executor(executor.clone())
+            .launch(())
+            .detach();
+        let context = glib::MainContext::default();
+
+        controller.emit(Msg::Compute(1));
+        while context.iteration(false) {}
+        // The output is sent, but the component didn't process it yet.
+        executor.run_all();
+        controller.model().handle.clone().unwrap().abort();
+        while context.iteration(false) {}
+        assert!(controller.model().results.is_empty());
+
+        controller.emit(Msg::Compute(2));
+        while context.iteration(false) {}
+        executor.run_all();
+        while context.iteration(false) {}
+        assert_eq!(controller.model().results, [2]);
+    }
+
+    #[gtk::test]
+    fn stream_command() {
+        let executor = ManualExecutor::new();
+        let builder = Search::builder().command_executor(executor.clone());
+        let mut tester = ComponentTester::from_builder(builder, ());
+
+        tester.send(Msg::Import);
+        executor.run_all();
+        tester.process();
+        assert_eq!(tester.command_outputs(), &[4, 5, 6]);
+    }
+
+    #[gtk::test]
+    fn finished_commands() {
+        let executor = ManualExecutor::new();
+        let builder = Search::builder().command_executor(executor.clone());
+        let mut tester = ComponentTester::from_builder(builder, ());
+
+        tester.send(Msg::Compute(1));
+        let completed = tester.model().handle.clone().unwrap();
+        assert!(!completed.is_finished());
+        executor.run_all();
+        assert!(completed.is_finished());
+        assert!(!completed.is_aborted());
+
+        tester.send(Msg::Compute(2));
+        let aborted = tester.model().handle.clone().unwrap();
+        tester.send(Msg::Cancel);
+        assert!(aborted.is_aborted());
+        assert!(!aborted.is_finished());
+        executor.run_all();
+        assert!(aborted.is_finished());
+    }
+}
@@ -3,11 +3,19 @@
 
 //! Contains various flavors of channels to send messages between components and workers.
 
-use std::fmt::Debug;
+use std::any;
+use std::fmt::{self, Debug};
 use std::future::Future;
+use std::hash::Hash;
 use std::sync::Arc;
+use std::time::Duration;
 
-use crate::component::AsyncComponent;
+use futures::{Stream, StreamExt};
+
+use super::command::{CommandHandle, CommandSender, KeyedCommands};
+use super::timer::{self, TimerHandle};
+use crate::component::{AsyncComponent, ErrorBoundary, TryComponent};
+use crate::executor::CommandExecutor;
 use crate::factory::{AsyncFactoryComponent, FactoryComponent};
 use crate::{Component, Sender, ShutdownReceiver};
 
@@ -23,8 +31,29 @@ where
     /// Emits component outputs.
     output: Sender<Output>,
     /// Emits command outputs.
-    command: Sender<CommandOutput>,
+    command: CommandSender<CommandOutput>,
     shutdown: ShutdownReceiver,
+    /// Runs commands.
+    executor: Arc<dyn CommandExecutor>,
+    /// Commands that were started with a key.
+    keyed: KeyedCommands,
+    /// Called for every output before it is sent.
+    on_output: Option<OutputHook<Output>>,
+}
+
+/// Observes the outputs of a component, for example to record them.
+pub(crate) struct OutputHook<Output>(Box<dyn Fn(&Output) + Send + Sync>);
+
+impl<Output> OutputHook<Output> {
+    pub(crate) fn new(hook: impl Fn(&Output) + Send + Sync + 'static) -> Self {
+        Self(Box::new(hook))
+    }
+}
+
+impl<Output> Debug for OutputHook<Output> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("OutputHook").finish_non_exhaustive()
+    }
 }
 
 impl<Input, Output, CommandOutput> ComponentSenderInner<Input, Output, CommandOutput>
@@ -56,7 +85,7 @@ where
     /// [`command()`][Self::command] is more concise.
     #[must_use]
     fn command_sender(&self) -> &Sender<CommandOutput> {
-        &self.command
+        self.command.untagged()
     }
 
     /// Emit an input to the component.
@@ -66,25 +95,50 @@ where
         self.input.send(message).expect("The runtime of the component was shutdown. Maybe you accidentally dropped a controller?");
     }
 
+    /// Emits an input every time the `period` has elapsed.
+    fn interval<F>(&self, period: Duration, message: F) -> TimerHandle
+    where
+        Input: 'static,
+        F: Fn() -> Input + 'static,
+    {
+        timer::interval(self.input.clone(), self.shutdown.clone(), period, message)
+    }
+
+    /// Emits an input once the `delay` has elapsed.
+    fn timeout(&self, delay: Duration, message: Input) -> TimerHandle
+    where
+        Input: 'static,
+    {
+        timer::timeout(self.input.clone(), self.shutdown.clone(), delay, message)
+    }
+
     /// This is not public because factories can unwrap the result
     /// because they keep the output receiver alive internally.
     fn output(&self, message: Output) -> Result<(), Output> {
+        if let Some(on_output) = &self.on_output {
+            (on_output.0)(&message);
+        }
         self.output.send(message)
     }
 
     /// Spawns an asynchronous command.
     /// You can bind the the command to the lifetime of the component
     /// by using a [`ShutdownReceiver`].
-    fn command<Cmd, Fut>(&self, cmd: Cmd)
+    fn command<Cmd, Fut>(&self, cmd: Cmd) -> CommandHandle
     where
         Cmd: FnOnce(Sender<CommandOutput>, ShutdownReceiver) -> Fut + Send + 'static,
         Fut: Future<Output = ()> + Send,
     {
+        let (handle, registration) = CommandHandle::new();
         let recipient = self.shutdown.clone();
-        let sender = self.command.clone();
-        crate::spawn(async move {
-            cmd(sender, recipient).await;
-        });
+        let sender = self.command.register(&handle);
+        self.executor.spawn(
+            any::type_name::<Cmd>(),
+            Box::pin(handle.wrap(registration, async move {
+                cmd(sender, recipient).await;
+            })),
+        );
+        handle
     }
 
     /// Spawns a synchronous command.
@@ -94,38 +148,117 @@ where
     ///
     /// If you expect the component to be dropped while
     /// the command is running take care while sending messages!
-    fn spawn_command<Cmd>(&self, cmd: Cmd)
+    fn spawn_command<Cmd>(&self, cmd: Cmd) -> CommandHandle
     where
         Cmd: FnOnce(Sender<CommandOutput>) + Send + 'static,
     {
-        let sender = self.command.clone();
-        crate::spawn_blocking(move || cmd(sender));
+        let (handle, _) = CommandHandle::new();
+        let command_handle = handle.clone();
+        let sender = self.command.register(&handle);
+        self.executor.spawn_blocking(
+            any::type_name::<Cmd>(),
+            Box::new(move || {
+                // Synchronous commands can only be aborted before they start.
+                if !command_handle.is_aborted() {
+                    cmd(sender);
+                }
+                command_handle.finish();
+            }),
+        );
+        handle
     }
 
     /// Spawns a future that will be dropped as soon as the factory component is shut down.
     ///
     /// Essentially, this is a simpler version of [`Self::command()`].
-    fn oneshot_command<Fut>(&self, future: Fut)
+    fn oneshot_command<Fut>(&self, future: Fut) -> CommandHandle
     where
         Fut: Future<Output = CommandOutput> + Send + 'static,
     {
-        // Async closures would be awesome here...
-        self.command(move |out, shutdown| {
-            shutdown
-                .register(async move { out.send(future.await) })
-                .drop_on_shutdown()
-        });
+        let (handle, registration) = CommandHandle::new();
+        let out = self.command.register(&handle);
+        let shutdown = self.shutdown.clone();
+        let command = shutdown
+            .register(async move {
+                out.send(future.await).ok();
+            })
+            .drop_on_shutdown();
+        self.executor.spawn(
+            any::type_name::<Fut>(),
+            Box::pin(handle.wrap(registration, command)),
+        );
+        handle
     }
 
     /// Spawns a synchronous command.
     ///
     /// Essentially, this is a simpler version of [`Self::spawn_command()`].
-    fn spawn_oneshot_command<Cmd>(&self, cmd: Cmd)
+    fn spawn_oneshot_command<Cmd>(&self, cmd: Cmd) -> CommandHandle
     where
         Cmd: FnOnce() -> CommandOutput + Send + 'static,
     {
-        let handle = crate::spawn_blocking(cmd);
-        self.oneshot_command(async move { handle.await.unwrap() })
+        // Synchronous commands can't be interrupted, so only their output is discarded.
+        let (handle, _) = CommandHandle::new();
+        let command_handle = handle.clone();
+
+        // Sending fails only if the component was shut down in the meantime.
+        let out = self.command.register(&handle);
+        self.executor.spawn_blocking(
+            any::type_name::<Cmd>(),
+            Box::new(move || {
+                if !command_handle.is_aborted() {
+                    out.send(cmd()).ok();
+                }
+                command_handle.finish();
+            }),
+        );
+        handle
+    }
+
+    /// Forwards the items of a stream as command outputs.
+    fn stream_command<S>(&self, stream: S) -> CommandHandle
+    where
+        S: Stream<Item = CommandOutput> + Send + 'static,
+    {
+        let (handle, registration) = CommandHandle::new();
+        let out = self.command.register(&handle);
+        let shutdown = self.shutdown.clone();
+        let command = shutdown
+            .register(async move {
+                futures::pin_mut!(stream);
+                while let Some(output) = stream.next().await {
+                    if out.send(output).is_err() {
+                        return;
+                    }
+                }
+            })
+            .drop_on_shutdown();
+        self.executor.spawn(
+            any::type_name::<S>(),
+            Box::pin(handle.wrap(registration, command)),
+        );
+        handle
+    }
+
+    /// Spawns an asynchronous command and aborts the previous command with the same key.
+    fn keyed_command<Cmd, Fut>(&self, key: impl Hash, cmd: Cmd) -> CommandHandle
+    where
+        Cmd: FnOnce(Sender<CommandOutput>, ShutdownReceiver) -> Fut + Send + 'static,
+        Fut: Future<Output = ()> + Send,
+    {
+        let handle = self.command(cmd);
+        self.keyed.replace(key, &handle);
+        handle
+    }
+
+    /// Spawns a future and aborts the previous command with the same key.
+    fn keyed_oneshot_command<Fut>(&self, key: impl Hash, future: Fut) -> CommandHandle
+    where
+        Fut: Future<Output = CommandOutput> + Send + 'static,
+    {
+        let handle = self.oneshot_command(future);
+        self.keyed.replace(key, &handle);
+        handle
     }
 }
 
@@ -141,8 +274,9 @@ macro_rules! sender_impl {
             pub(crate) fn new(
                 input: Sender<C::Input>,
                 output: Sender<C::Output>,
-                command: Sender<C::CommandOutput>,
+                command: CommandSender<C::CommandOutput>,
                 shutdown: ShutdownReceiver,
+                executor: Arc<dyn CommandExecutor>,
             ) -> Self {
                 Self {
                     shared: Arc::new(ComponentSenderInner {
@@ -150,6 +284,9 @@ macro_rules! sender_impl {
                         output,
                         command,
                         shutdown,
+                        executor,
+                        keyed: KeyedCommands::default(),
+                        on_output: None,
                     }),
                 }
             }
@@ -186,6 +323,37 @@ macro_rules! sender_impl {
                 self.shared.input(message);
             }
 
+            /// Emits an input every time the `period` has elapsed, starting after the first period.
+            ///
+            /// The timer runs on the main thread and stops when the component is shut down
+            /// or the returned handle is cancelled.
+            ///
+            /// ```
+            /// # use std::time::Duration;
+            /// # use relm4::ComponentSender;
+            /// # use relm4::prelude::*;
+            /// # #[derive(Debug)]
+            /// # enum Msg { Tick }
+            /// # fn start<C: Component<Input = Msg>>(sender: ComponentSender<C>) {
+            /// let clock = sender.interval(Duration::from_secs(1), || Msg::Tick);
+            /// # clock.cancel();
+            /// # }
+            /// ```
+            pub fn interval<F>(&self, period: Duration, message: F) -> TimerHandle
+            where
+                F: Fn() -> C::Input + 'static,
+            {
+                self.shared.interval(period, message)
+            }
+
+            /// Emits an input once the `delay` has elapsed.
+            ///
+            /// Like [`interval()`](Self::interval), the timer stops when the component
+            /// is shut down or the returned handle is cancelled.
+            pub fn timeout(&self, delay: Duration, message: C::Input) -> TimerHandle {
+                self.shared.timeout(delay, message)
+            }
+
             /// Emit an output to the component.
             ///
             /// Returns [`Err`] if all receivers were dropped,
@@ -199,7 +367,9 @@ macro_rules! sender_impl {
             /// Spawns an asynchronous command.
             /// You can bind the the command to the lifetime of the component
             /// by using a [`ShutdownReceiver`].
-            pub fn command<Cmd, Fut>(&self, cmd: Cmd)
+            ///
+            /// The returned [`CommandHandle`] can abort the command.
+            pub fn command<Cmd, Fut>(&self, cmd: Cmd) -> CommandHandle
             where
                 Cmd: FnOnce(Sender<C::CommandOutput>, ShutdownReceiver) -> Fut + Send + 'static,
                 Fut: Future<Output = ()> + Send,
@@ -214,7 +384,10 @@ macro_rules! sender_impl {
             ///
             /// If you expect the component to be dropped while
             /// the command is running take care while sending messages!
-            pub fn spawn_command<Cmd>(&self, cmd: Cmd)
+            ///
+            /// The returned [`CommandHandle`] can't interrupt the command once it runs,
+            /// but aborting it discards all outputs the component didn't process yet.
+            pub fn spawn_command<Cmd>(&self, cmd: Cmd) -> CommandHandle
             where
                 Cmd: FnOnce(Sender<C::CommandOutput>) + Send + 'static,
             {
@@ -224,7 +397,7 @@ macro_rules! sender_impl {
             /// Spawns a future that will be dropped as soon as the factory component is shut down.
             ///
             /// Essentially, this is a simpler version of [`Self::command()`].
-            pub fn oneshot_command<Fut>(&self, future: Fut)
+            pub fn oneshot_command<Fut>(&self, future: Fut) -> CommandHandle
             where
                 Fut: Future<Output = C::CommandOutput> + Send + 'static,
             {
@@ -234,12 +407,65 @@ macro_rules! sender_impl {
             /// Spawns a synchronous command that will be dropped as soon as the factory component is shut down.
             ///
             /// Essentially, this is a simpler version of [`Self::spawn_command()`].
-            pub fn spawn_oneshot_command<Cmd>(&self, cmd: Cmd)
+            pub fn spawn_oneshot_command<Cmd>(&self, cmd: Cmd) -> CommandHandle
             where
                 Cmd: FnOnce() -> C::CommandOutput + Send + 'static,
             {
                 self.shared.spawn_oneshot_command(cmd)
             }
+
+            /// Spawns a command that passes every item of a stream to `update_cmd`.
+            ///
+            /// This is useful for long-running jobs that report their progress
+            /// before they finish with a final value.
+            /// Like [`oneshot_command()`](Self::oneshot_command), the stream
+            /// is dropped as soon as the component is shut down.
+            ///
+            /// ```
+            /// # use relm4::prelude::*;
+            /// # use relm4::ComponentSender;
+            /// #[derive(Debug)]
+            /// enum CommandMsg {
+            ///     Progress(f64),
+            ///     Finished,
+            /// }
+            ///
+            /// # fn import<C: Component<CommandOutput = CommandMsg>>(sender: ComponentSender<C>) {
+            /// let progress = (1..10).map(|step| CommandMsg::Progress(f64::from(step) / 10.0));
+            /// let steps = futures::stream::iter(progress.chain([CommandMsg::Finished]));
+            /// sender.stream_command(steps);
+            /// # }
+            /// ```
+            pub fn stream_command<S>(&self, stream: S) -> CommandHandle
+            where
+                S: Stream<Item = C::CommandOutput> + Send + 'static,
+            {
+                self.shared.stream_command(stream)
+            }
+
+            /// Spawns an asynchronous command like [`command()`](Self::command)
+            /// and aborts the previous command that was started with the same key.
+            ///
+            /// This is useful for requests where only the latest result matters,
+            /// like search suggestions.
+            pub fn keyed_command<K, Cmd, Fut>(&self, key: K, cmd: Cmd) -> CommandHandle
+            where
+                K: Hash,
+                Cmd: FnOnce(Sender<C::CommandOutput>, ShutdownReceiver) -> Fut + Send + 'static,
+                Fut: Future<Output = ()> + Send,
+            {
+                self.shared.keyed_command(key, cmd)
+            }
+
+            /// Spawns a future like [`oneshot_command()`](Self::oneshot_command)
+            /// and aborts the previous command that was started with the same key.
+            pub fn keyed_oneshot_command<K, Fut>(&self, key: K, future: Fut) -> CommandHandle
+            where
+                K: Hash,
+                Fut: Future<Output = C::CommandOutput> + Send + 'static,
+            {
+                self.shared.keyed_oneshot_command(key, future)
+            }
         }
 
         impl<C: $trait> Clone for $name<C> {
@@ -256,3 +482,45 @@ sender_impl!(ComponentSender, Component);
 sender_impl!(AsyncComponentSender, AsyncComponent);
 sender_impl!(FactorySender, FactoryComponent);
 sender_impl!(AsyncFactorySender, AsyncFactoryComponent);
+sender_impl!(TryComponentSender, TryComponent);
+
+impl<C: Component> ComponentSender<C> {
+    /// Creates a sender that passes every output to `on_output` before sending it.
+    pub(crate) fn with_output_hook(
+        input: Sender<C::Input>,
+        output: Sender<C::Output>,
+        command: CommandSender<C::CommandOutput>,
+        shutdown: ShutdownReceiver,
+        executor: Arc<dyn CommandExecutor>,
+        on_output: Option<OutputHook<C::Output>>,
+    ) -> Self {
+        Self {
+            shared: Arc::new(ComponentSenderInner {
+                input,
+                output,
+                command,
+                shutdown,
+                executor,
+                keyed: KeyedCommands::default(),
+                on_output,
+            }),
+        }
+    }
+}
+
+impl<C: TryComponent> TryComponentSender<C> {
+    /// Creates the sender of a component that runs inside an [`ErrorBoundary`].
+    pub(crate) fn from_boundary(
+        sender: &ComponentSender<ErrorBoundary<C>>,
+        output: Sender<C::Output>,
+    ) -> Self {
+        let shared = &sender.shared;
+        Self::new(
+            shared.input.clone(),
+            output,
+            shared.command.clone(),
+            shared.shutdown.clone(),
+            shared.executor.clone(),
+        )
+    }
+}
@@ -1,30 +1,158 @@
+mod command;
 mod component;
+mod reply;
 /// Cancellation mechanism used by Relm4.
 pub mod shutdown;
+mod timer;
 
-pub use component::{AsyncComponentSender, AsyncFactorySender, ComponentSender, FactorySender};
+pub use command::CommandHandle;
+pub(crate) use command::{command_channel, CommandReceiver, CommandSender};
+pub(crate) use component::OutputHook;
+pub use component::{
+    AsyncComponentSender, AsyncFactorySender, ComponentSender, FactorySender, TryComponentSender,
+};
+pub use reply::{Reply, Response};
+pub use timer::TimerHandle;
 
 // Copyright 2022 System76 <info@system76.com>
 // SPDX-License-Identifier: MIT or Apache-2.0
 
 use std::fmt;
+use std::mem;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
 
 use flume::r#async::RecvStream;
+use flume::{SendTimeoutError, TrySendError};
 
 /// Create an unbounded channel to send messages
 /// between different parts of you application.
 #[must_use]
 pub fn channel<T>() -> (Sender<T>, Receiver<T>) {
     let (tx, rx) = flume::unbounded();
-    (Sender(tx), Receiver(rx))
+    (Sender(tx, None), Receiver(rx))
+}
+
+/// Create a channel that holds at most `capacity` messages.
+///
+/// The `policy` decides what happens to messages that are
+/// sent while the channel is full.
+///
+/// # Panics
+///
+/// Panics if `capacity` is zero.
+#[must_use]
+pub fn bounded_channel<T>(capacity: usize, policy: OverflowPolicy) -> (Sender<T>, Receiver<T>) {
+    assert!(
+        capacity > 0,
+        "A bounded channel needs room for at least one message"
+    );
+    let (tx, rx) = flume::bounded(capacity);
+    let bounded = Bounded {
+        policy,
+        receiver: rx.clone(),
+        lock: Mutex::new(()),
+    };
+    (Sender(tx, Some(Arc::new(bounded))), Receiver(rx))
+}
+
+/// Decides what happens to messages that are sent to a full [bounded channel](bounded_channel).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum OverflowPolicy {
+    /// Wait until there's room for the message.
+    ///
+    /// This blocks the sending thread, so only use it if messages are sent from
+    /// other threads than the one the receiver runs on, for example from
+    /// commands or to a worker.
+    /// Otherwise, the application freezes as soon as the channel is full.
+    Block,
+    /// Drop the message that is sent.
+    DropNewest,
+    /// Drop the oldest queued message to make room for the new one.
+    DropOldest,
+    /// Drop the queued messages of the same enum variant as the new message
+    /// so only the latest one is kept.
+    ///
+    /// If no such message is queued, the oldest message is dropped instead.
+    /// Variants are compared with [`mem::discriminant`].
+    Coalesce,
+}
+
+/// How often a blocked sender checks whether the receiver was dropped.
+const BLOCK_INTERVAL: Duration = Duration::from_millis(100);
+
+struct Bounded<T> {
+    policy: OverflowPolicy,
+    // Used to remove queued messages.
+    // Therefore, the channel is disconnected once this is the last receiver.
+    receiver: flume::Receiver<T>,
+    // Prevents other senders from filling the channel while it is rearranged.
+    lock: Mutex<()>,
+}
+
+impl<T> Bounded<T> {
+    fn is_disconnected(&self) -> bool {
+        self.receiver.receiver_count() <= 1
+    }
+
+    fn send(&self, sender: &flume::Sender<T>, mut message: T) -> Result<(), T> {
+        if self.policy == OverflowPolicy::Block {
+            loop {
+                if self.is_disconnected() {
+                    return Err(message);
+                }
+                match sender.send_timeout(message, BLOCK_INTERVAL) {
+                    Ok(()) => return Ok(()),
+                    Err(SendTimeoutError::Timeout(msg)) => message = msg,
+                    Err(SendTimeoutError::Disconnected(msg)) => return Err(msg),
+                }
+            }
+        }
+
+        let _guard = self.lock.lock().unwrap();
+        if self.is_disconnected() {
+            return Err(message);
+        }
+
+        let message = match sender.try_send(message) {
+            Err(TrySendError::Full(message)) => message,
+            result => return result.map_err(TrySendError::into_inner),
+        };
+
+        match self.policy {
+            OverflowPolicy::Block => unreachable!(),
+            OverflowPolicy::DropNewest => return Ok(()),
+            OverflowPolicy::DropOldest => {
+                self.receiver.try_recv().ok();
+            }
+            OverflowPolicy::Coalesce => {
+                let variant = mem::discriminant(&message);
+                let mut queued: Vec<T> = self.receiver.drain().collect();
+                let count = queued.len();
+                queued.retain(|queued| mem::discriminant(queued) != variant);
+                if queued.len() == count && !queued.is_empty() {
+                    queued.remove(0);
+                }
+
+                // The lock guarantees that the remaining messages fit.
+                for queued in queued {
+                    sender.try_send(queued).ok();
+                }
+            }
+        }
+
+        // The receiver might have taken the message in the meantime
+        // and there's room for at least one message now.
+        sender.try_send(message).map_err(TrySendError::into_inner)
+    }
 }
 
 /// A Relm4 sender sends messages to a component or worker.
-pub struct Sender<T>(pub(crate) flume::Sender<T>);
+pub struct Sender<T>(pub(crate) flume::Sender<T>, Option<Arc<Bounded<T>>>);
 
 impl<T> From<flume::Sender<T>> for Sender<T> {
     fn from(sender: flume::Sender<T>) -> Self {
-        Self(sender)
+        Self(sender, None)
     }
 }
 
@@ -43,14 +171,21 @@ impl<T> Sender<T> {
     ///
     /// If all receivers where dropped, [`Err`] is returned
     /// with the content of the message.
+    ///
+    /// For [bounded channels](bounded_channel), the [`OverflowPolicy`]
+    /// decides what happens if the channel is full.
     pub fn send(&self, message: T) -> Result<(), T> {
-        self.0.send(message).map_err(|e| e.into_inner())
+        if let Some(bounded) = &self.1 {
+            bounded.send(&self.0, message)
+        } else {
+            self.0.send(message).map_err(|e| e.into_inner())
+        }
     }
 }
 
 impl<T> Clone for Sender<T> {
     fn clone(&self) -> Self {
-        Self(self.0.clone())
+        Self(self.0.clone(), self.1.clone())
     }
 }
 
@@ -109,3 +244,60 @@ impl<T> fmt::Debug for Receiver<T> {
         f.debug_tuple("Receiver").field(&self.0).finish()
     }
 }
+
+#[cfg(test)]
+mod test {
+    use super::{bounded_channel, OverflowPolicy};
+
+    #[derive(Debug, PartialEq, Eq)]
+    enum Msg {
+        Progress(u8),
+        Done,
+    }
+
+    fn received(policy: OverflowPolicy) -> Vec<Msg> {
+        let (sender, receiver) = bounded_channel(3, policy);
+        sender.emit(Msg::Progress(1));
+        sender.emit(Msg::Done);
+        sender.emit(Msg::Progress(2));
+        sender.emit(Msg::Progress(3));
+        receiver.0.try_iter().collect()
+    }
+
+    #[test]
+    fn drop_newest() {
+        assert_eq!(
+            received(OverflowPolicy::DropNewest),
+            [Msg::Progress(1), Msg::Done, Msg::Progress(2)]
+        );
+    }
+
+    #[test]
+    fn drop_oldest() {
+        assert_eq!(
+            received(OverflowPolicy::DropOldest),
+            [Msg::Done, Msg::Progress(2), Msg::Progress(3)]
+        );
+    }
+
+    #[test]
+    fn coalesce() {
+        assert_eq!(
+            received(OverflowPolicy::Coalesce),
+            [Msg::Done, Msg::Progress(3)]
+        );
+    }
+
+    #[test]
+    fn disconnected() {
+        let (sender, receiver) = bounded_channel(1, OverflowPolicy::Block);
+        drop(receiver);
+        assert_eq!(sender.send(()), Err(()));
+    }
+
+    #[test]
+    #[should_panic(expected = "at least one message")]
+    fn zero_capacity() {
+        let _ = bounded_channel::<()>(0, OverflowPolicy::Block);
+    }
+}
@@ -0,0 +1,169 @@
+use std::fmt;
+use std::future::Future;
+use std::pin::Pin;
+use std::task::{Context, Poll};
+
+use tokio::sync::oneshot;
+
+use super::Sender;
+
+/// Sends the reply to a request back to the component that sent it.
+///
+/// A [`Reply`] is passed as part of a message, usually by
+/// [`Sender::request()`] or [`ComponentController::request()`](crate::ComponentController::request).
+/// The receiving component answers with [`send()`](Self::send) once it has the result,
+/// which doesn't need to happen in the same update.
+///
+/// ```
+/// use relm4::Reply;
+///
+/// #[derive(Debug)]
+/// enum Msg {
+///     Increment,
+///     Query(Reply<u32>),
+/// }
+/// ```
+pub struct Reply<T>(oneshot::Sender<T>);
+
+impl<T> Reply<T> {
+    /// Creates a reply and the [`Response`] that receives it.
+    pub fn new() -> (Self, Response<T>) {
+        let (sender, receiver) = oneshot::channel();
+        (Self(sender), Response(receiver))
+    }
+
+    /// Sends the reply.
+    ///
+    /// **This method ignores errors.**
+    /// The reply is dropped if the requester isn't waiting for it anymore.
+    pub fn send(self, value: T) {
+        self.0.send(value).ok();
+    }
+
+    /// Returns [`true`] if the requester isn't waiting for the reply anymore.
+    #[must_use]
+    pub fn is_closed(&self) -> bool {
+        self.0.is_closed()
+    }
+}
+
+impl<T> fmt::Debug for Reply<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Reply").finish_non_exhaustive()
+    }
+}
+
+/// A future that resolves to the reply to a request.
+///
+/// Resolves to [`None`] if the [`Reply`] was dropped without an answer,
+/// for example because the component was shut down.
+#[must_use = "futures do nothing unless polled"]
+pub struct Response<T>(oneshot::Receiver<T>);
+
+impl<T> Future for Response<T> {
+    type Output = Option<T>;
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        Pin::new(&mut self.0).poll(cx).map(Result::ok)
+    }
+}
+
+impl<T> fmt::Debug for Response<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Response").finish_non_exhaustive()
+    }
+}
+
+impl<T> Sender<T> {
+    /// Sends a message that carries a [`Reply`] and returns
+    /// a future that resolves to the reply.
+    ///
+    /// ```
+    /// # use relm4::{Reply, Sender};
+    /// # #[derive(Debug)]
+    /// # enum Msg { Query(Reply<u32>) }
+    /// # async fn query(sender: Sender<Msg>) {
+    /// let value: Option<u32> = sender.request(Msg::Query).await;
+    /// # }
+    /// ```
+    pub fn request<R>(&self, message: impl FnOnce(Reply<R>) -> T) -> Response<R> {
+        let (reply, response) = Reply::new();
+        // If the receiver was dropped, so is the reply.
+        self.send(message(reply)).ok();
+        response
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::cell::Cell;
+    use std::rc::Rc;
+
+    use futures::FutureExt;
+
+    use super::Reply;
+    use crate::testing::ComponentTester;
+    use crate::{ComponentController, ComponentParts, ComponentSender, SimpleComponent};
+
+    #[derive(Debug)]
+    enum Msg {
+        Increment,
+        Query(Reply<u8>),
+    }
+
+    struct Counter(u8);
+
+    impl SimpleComponent for Counter {
+        type Input = Msg;
+        type Output = ();
+        type Init = ();
+        type Root = ();
+        type Widgets = ();
+
+        fn init_root() -> Self::Root {}
+
+        fn init(
+            _init: Self::Init,
+            _root: Self::Root,
+            _sender: ComponentSender<Self>,
+        ) -> ComponentParts<Self> {
+            ComponentParts {
+                model: Counter(0),
+                widgets: (),
+            }
+        }
+
+        fn update(&mut self, message: Self::Input, _sender: ComponentSender<Self>) {
+            match message {
+                Msg::Increment => self.0 += 1,
+                Msg::Query(reply) => reply.send(self.0),
+            }
+        }
+    }
+
+    #[gtk::test]
+    fn request_reply() {
+        let mut tester = ComponentTester::<Counter>::new(());
+        tester.send(Msg::Increment);
+
+        let first = tester.controller().sender().request(Msg::Query);
+        tester.send(Msg::Increment);
+        let second = tester.controller().sender().request(Msg::Query);
+        tester.process();
+
+        assert_eq!(first.now_or_never(), Some(Some(1)));
+        assert_eq!(second.now_or_never(), Some(Some(2)));
+
+        let received = Rc::new(Cell::new(None));
+        let result = received.clone();
+        tester
+            .controller()
+            .request(Msg::Query, move |value| result.set(Some(value)));
+        tester.process();
+        assert_eq!(received.get(), Some(2));
+
+        let sender = tester.controller().sender().clone();
+        drop(tester.shutdown());
+        assert_eq!(sender.request(Msg::Query).now_or_never(), Some(None));
+    }
+}
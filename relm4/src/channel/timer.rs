@@ -0,0 +1,135 @@
+use std::fmt;
+use std::time::Duration;
+
+use gtk::glib;
+
+use super::Sender;
+use crate::ShutdownReceiver;
+
+/// Cancels a timer started by the `interval` or `timeout` method of a component sender.
+///
+/// Timers stop automatically when the component is shut down.
+/// Dropping the handle doesn't cancel the timer.
+pub struct TimerHandle(glib::JoinHandle<()>);
+
+impl TimerHandle {
+    /// Stops the timer, so it doesn't send any further messages.
+    pub fn cancel(self) {
+        self.0.abort();
+    }
+}
+
+impl fmt::Debug for TimerHandle {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("TimerHandle").finish_non_exhaustive()
+    }
+}
+
+/// Sends the message returned by `message` every time the `period` has elapsed.
+pub(super) fn interval<T: 'static>(
+    sender: Sender<T>,
+    shutdown: ShutdownReceiver,
+    period: Duration,
+    message: impl Fn() -> T + 'static,
+) -> TimerHandle {
+    let timer = async move {
+        loop {
+            crate::clock::sleep(period).await;
+            if sender.send(message()).is_err() {
+                return;
+            }
+        }
+    };
+    TimerHandle(crate::spawn_local(
+        shutdown.register(timer).drop_on_shutdown(),
+    ))
+}
+
+/// Sends `message` once the `delay` has elapsed.
+pub(super) fn timeout<T: 'static>(
+    sender: Sender<T>,
+    shutdown: ShutdownReceiver,
+    delay: Duration,
+    message: T,
+) -> TimerHandle {
+    let timer = async move {
+        crate::clock::sleep(delay).await;
+        sender.send(message).ok();
+    };
+    TimerHandle(crate::spawn_local(
+        shutdown.register(timer).drop_on_shutdown(),
+    ))
+}
+
+#[cfg(test)]
+mod test {
+    use std::time::Duration;
+
+    use super::TimerHandle;
+    use crate::testing::ComponentTester;
+    use crate::{ComponentParts, ComponentSender, SimpleComponent};
+
+    #[derive(Debug)]
+    enum Msg {
+        Tick,
+        Done,
+        Stop,
+    }
+
+    struct Clock {
+        ticks: u8,
+        done: bool,
+        interval: Option<TimerHandle>,
+    }
+
+    impl SimpleComponent for Clock {
+        type Input = Msg;
+        type Output = ();
+        type Init = ();
+        type Root = ();
+        type Widgets = ();
+
+        fn init_root() -> Self::Root {}
+
+        fn init(
+            _init: Self::Init,
+            _root: Self::Root,
+            sender: ComponentSender<Self>,
+        ) -> ComponentParts<Self> {
+            let interval = sender.interval(Duration::from_millis(1), || Msg::Tick);
+            sender.timeout(Duration::from_millis(5), Msg::Done);
+            ComponentParts {
+                model: Clock {
+                    ticks: 0,
+                    done: false,
+                    interval: Some(interval),
+                },
+                widgets: (),
+            }
+        }
+
+        fn update(&mut self, message: Self::Input, _sender: ComponentSender<Self>) {
+            match message {
+                Msg::Tick => self.ticks += 1,
+                Msg::Done => self.done = true,
+                Msg::Stop => self.interval.take().unwrap().cancel(),
+            }
+        }
+    }
+
+    #[gtk::test]
+    fn interval_and_timeout() {
+        let mut tester = ComponentTester::<Clock>::new(());
+        tester.advance(Duration::from_millis(4));
+        assert_eq!(tester.model().ticks, 4);
+        assert!(!tester.model().done);
+
+        tester.advance(Duration::from_millis(1));
+        assert_eq!(tester.model().ticks, 5);
+        assert!(tester.model().done);
+
+        tester.send(Msg::Stop);
+        tester.advance(Duration::from_millis(5));
+        assert_eq!(tester.model().ticks, 5);
+    }
+}
@@ -0,0 +1,135 @@
+//! The clock used by timers and delayed inputs of components.
+//!
+//! Usually, this is the time of the system. While a
+//! [`ComponentTester`](crate::testing::ComponentTester) is alive, components
+//! on its thread use a virtual clock instead, which only moves when the test advances it.
+
+use std::cell::RefCell;
+use std::future::Future;
+use std::mem;
+use std::pin::Pin;
+use std::sync::{Arc, Mutex, Weak};
+use std::task::{Context, Poll, Waker};
+use std::time::{Duration, Instant};
+
+use gtk::glib;
+
+thread_local! {
+    static VIRTUAL_CLOCK: RefCell<Weak<VirtualClock>> = RefCell::default();
+}
+
+pub(crate) type Sleep = Pin<Box<dyn Future<Output = ()> + Send + 'static>>;
+
+fn virtual_clock() -> Option<Arc<VirtualClock>> {
+    VIRTUAL_CLOCK.with(|clock| clock.borrow().upgrade())
+}
+
+/// Returns the current time of the clock of this thread.
+pub(crate) fn now() -> Instant {
+    virtual_clock().map_or_else(Instant::now, |clock| clock.now())
+}
+
+/// Completes once `duration` has elapsed on the clock of this thread.
+pub(crate) fn sleep(duration: Duration) -> Sleep {
+    match virtual_clock() {
+        Some(clock) => {
+            let deadline = clock.now() + duration;
+            Box::pin(VirtualSleep { clock, deadline })
+        }
+        None => glib::timeout_future(duration),
+    }
+}
+
+/// A clock that only moves when it's advanced.
+#[derive(Debug)]
+pub(crate) struct VirtualClock {
+    state: Mutex<State>,
+}
+
+#[derive(Debug)]
+struct State {
+    now: Instant,
+    timers: Vec<(Instant, Waker)>,
+}
+
+impl VirtualClock {
+    /// Returns the virtual clock of this thread and creates it if needed.
+    ///
+    /// The thread uses the virtual clock as long as it's referenced.
+    pub(crate) fn current() -> Arc<Self> {
+        VIRTUAL_CLOCK.with(|cell| {
+            let mut cell = cell.borrow_mut();
+            cell.upgrade().unwrap_or_else(|| {
+                let clock = Arc::new(Self {
+                    state: Mutex::new(State {
+                        now: Instant::now(),
+                        timers: Vec::new(),
+                    }),
+                });
+                *cell = Arc::downgrade(&clock);
+                clock
+            })
+        })
+    }
+
+    pub(crate) fn now(&self) -> Instant {
+        self.state.lock().unwrap().now
+    }
+
+    /// Moves the clock to the next deadline up to `target` and wakes the timers that are due.
+    ///
+    /// Returns [`false`] and moves the clock to `target` if no timer is due until then.
+    pub(crate) fn advance_to_next(&self, target: Instant) -> bool {
+        let mut state = self.state.lock().unwrap();
+        let next = state
+            .timers
+            .iter()
+            .map(|(deadline, _)| *deadline)
+            .filter(|deadline| *deadline <= target)
+            .min();
+        let Some(next) = next else {
+            state.now = state.now.max(target);
+            return false;
+        };
+
+        state.now = state.now.max(next);
+        let now = state.now;
+        let (due, pending): (Vec<_>, Vec<_>) = mem::take(&mut state.timers)
+            .into_iter()
+            .partition(|(deadline, _)| *deadline <= now);
+        state.timers = pending;
+
+        // Wake the timers without holding the lock.
+        drop(state);
+        for (_, waker) in due {
+            waker.wake();
+        }
+        true
+    }
+}
+
+struct VirtualSleep {
+    clock: Arc<VirtualClock>,
+    deadline: Instant,
+}
+
+impl Future for VirtualSleep {
+    type Output = ();
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let mut state = self.clock.state.lock().unwrap();
+        if state.now >= self.deadline {
+            Poll::Ready(())
+        } else {
+            let deadline = self.deadline;
+            let registered = state
+                .timers
+                .iter()
+                .any(|(due, waker)| *due == deadline && waker.will_wake(cx.waker()));
+            if !registered {
+                state.timers.push((deadline, cx.waker().clone()));
+            }
+            Poll::Pending
+        }
+    }
+}
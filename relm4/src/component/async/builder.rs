@@ -5,6 +5,8 @@
 use super::super::MessageBroker;
 use super::{AsyncComponent, AsyncComponentParts, AsyncConnector};
 use crate::channel::AsyncComponentSender;
+use crate::executor::{self, CommandExecutor};
+use crate::inspector::{self, ComponentKind, Inspection};
 use crate::{
     late_initialization, GuardedReceiver, Receiver, RelmContainerExt, RelmWidgetExt,
     RuntimeSenders, Sender,
@@ -12,7 +14,9 @@ use crate::{
 use gtk::glib;
 use gtk::prelude::{GtkWindowExt, NativeDialogExt};
 use std::any;
+use std::fmt::Debug;
 use std::marker::PhantomData;
+use std::sync::Arc;
 use tracing::info_span;
 
 /// A component that is ready for docking and launch.
@@ -21,6 +25,8 @@ pub struct AsyncComponentBuilder<C: AsyncComponent> {
     /// The root widget of the component.
     pub root: C::Root,
     priority: glib::Priority,
+    pub(crate) executor: Option<Arc<dyn CommandExecutor>>,
+    debug_model: Option<fn(&C) -> String>,
 
     pub(super) component: PhantomData<C>,
 }
@@ -31,6 +37,8 @@ impl<C: AsyncComponent> Default for AsyncComponentBuilder<C> {
         Self {
             root: C::init_root(),
             priority: glib::Priority::default(),
+            executor: None,
+            debug_model: None,
             component: PhantomData,
         }
     }
@@ -60,6 +68,25 @@ impl<C: AsyncComponent> AsyncComponentBuilder<C> {
         self.priority = priority;
         self
     }
+
+    /// Run the commands of this component on a custom [`CommandExecutor`].
+    ///
+    /// By default, the executor of the current thread is used,
+    /// see [`set_thread_executor()`](crate::executor::set_thread_executor).
+    #[must_use]
+    pub fn command_executor(mut self, executor: impl CommandExecutor) -> Self {
+        self.executor = Some(Arc::new(executor));
+        self
+    }
+}
+
+impl<C: AsyncComponent + Debug> AsyncComponentBuilder<C> {
+    /// Show a [`Debug`] view of the model in the [`inspector`](crate::inspector).
+    #[must_use]
+    pub fn inspect(mut self) -> Self {
+        self.debug_model = Some(inspector::debug_model);
+        self
+    }
 }
 
 impl<C: AsyncComponent> AsyncComponentBuilder<C>
@@ -165,7 +192,13 @@ impl<C: AsyncComponent> AsyncComponentBuilder<C> {
         input_sender: Sender<C::Input>,
         input_receiver: Receiver<C::Input>,
     ) -> AsyncConnector<C> {
-        let Self { root, priority, .. } = self;
+        let Self {
+            root,
+            priority,
+            executor,
+            debug_model,
+            ..
+        } = self;
         let temp_widgets = C::init_loading_widgets(root.clone());
 
         let RuntimeSenders {
@@ -185,6 +218,14 @@ impl<C: AsyncComponent> AsyncComponentBuilder<C> {
             output_sender.clone(),
             cmd_sender,
             shutdown_recipient,
+            executor.unwrap_or_else(executor::thread_executor),
+        );
+
+        let inspection = Inspection::register(
+            ComponentKind::AsyncComponent,
+            &input_sender,
+            C::id,
+            debug_model,
         );
 
         let rt_root = root.clone();
@@ -196,7 +237,11 @@ impl<C: AsyncComponent> AsyncComponentBuilder<C> {
             let mut state = C::init(payload, rt_root.clone(), component_sender.clone()).await;
             drop(temp_widgets);
 
-            let mut cmd = GuardedReceiver::new(cmd_receiver);
+            if let Some(inspection) = &inspection {
+                inspection.update(&state.model);
+            }
+
+            let mut cmd = cmd_receiver;
             let mut input = GuardedReceiver::new(input_receiver);
 
             loop {
@@ -218,6 +263,10 @@ impl<C: AsyncComponent> AsyncComponentBuilder<C> {
                         let _enter = span.enter();
 
                         model.update_with_view(widgets, message, component_sender.clone(), &rt_root).await;
+
+                        if let Some(inspection) = &inspection {
+                            inspection.update(model);
+                        }
                     }
 
                     // Handles responses from a command.
@@ -236,6 +285,10 @@ impl<C: AsyncComponent> AsyncComponentBuilder<C> {
                         let _enter = span.enter();
 
                         model.update_cmd_with_view(widgets, message, component_sender.clone(), &rt_root).await;
+
+                        if let Some(inspection) = &inspection {
+                            inspection.update(model);
+                        }
                     }
 
                     // Triggered when the component is destroyed
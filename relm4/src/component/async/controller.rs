@@ -4,7 +4,7 @@
 
 use std::fmt::{self, Debug};
 
-use crate::{Sender, ShutdownOnDrop};
+use crate::{Reply, Sender, ShutdownOnDrop};
 
 use super::AsyncComponent;
 
@@ -18,6 +18,26 @@ pub trait AsyncComponentController<C: AsyncComponent> {
     /// Provides access to the component's sender.
     fn sender(&self) -> &Sender<C::Input>;
 
+    /// Sends a request to the component and calls `callback` with the reply.
+    ///
+    /// The callback is called on the main thread once the component answered.
+    /// If the component drops the [`Reply`] without answering, the callback isn't called.
+    /// Use [`Sender::request()`] to await the reply instead.
+    fn request<R: 'static>(
+        &self,
+        message: impl FnOnce(Reply<R>) -> C::Input,
+        callback: impl FnOnce(R) + 'static,
+    ) where
+        Self: Sized,
+    {
+        let response = self.sender().request(message);
+        crate::spawn_local(async move {
+            if let Some(reply) = response.await {
+                callback(reply);
+            }
+        });
+    }
+
     /// Returns the root widget of the component.
     fn widget(&self) -> &C::Root;
 
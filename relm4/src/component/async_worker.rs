@@ -0,0 +1,355 @@
+use std::fmt::{self, Debug};
+use std::future::Future;
+use std::marker::PhantomData;
+use std::sync::Arc;
+
+use futures::FutureExt;
+use tokio::sync::Semaphore;
+
+use crate::{GuardedReceiver, Receiver, RuntimeSenders, Sender, ShutdownOnDrop};
+
+/// Asynchronous variant of [`Worker`](crate::Worker).
+///
+/// Instead of running on a dedicated thread, async workers run on the
+/// shared runtime of Relm4, so they don't block an OS thread while they wait
+/// for I/O.
+/// The number of threads of the runtime can be configured with
+/// [`RELM_THREADS`](crate::RELM_THREADS).
+///
+/// Updates only get a shared reference to the worker because they may run
+/// concurrently, see [`AsyncWorkerBuilder::concurrency()`].
+/// Use interior mutability for state that changes.
+///
+/// ```
+/// use relm4::component::AsyncWorker;
+/// use relm4::Sender;
+///
+/// struct Downloader {
+///     base_url: String,
+/// }
+///
+/// impl AsyncWorker for Downloader {
+///     type Init = String;
+///     type Input = String;
+///     type Output = Vec<u8>;
+///
+///     async fn init(base_url: Self::Init) -> Self {
+///         Self { base_url }
+///     }
+///
+///     async fn update(&self, path: Self::Input, output: Sender<Self::Output>) {
+///         let url = format!("{}/{path}", self.base_url);
+///         // Download the file...
+///         # drop(url);
+///         output.emit(Vec::new());
+///     }
+/// }
+/// ```
+pub trait AsyncWorker: Sized + Send + Sync + 'static {
+    /// The initial parameters that will be used to build the worker state.
+    type Init: Send + 'static;
+    /// The type of inputs that this worker shall receive.
+    type Input: Send + Debug + 'static;
+    /// The type of outputs that this worker shall send.
+    type Output: Send + Debug + 'static;
+
+    /// Create a builder for this worker.
+    #[must_use]
+    fn builder() -> AsyncWorkerBuilder<Self> {
+        AsyncWorkerBuilder::default()
+    }
+
+    /// Defines the initial state of the worker.
+    ///
+    /// Inputs that are sent in the meantime are queued.
+    fn init(init: Self::Init) -> impl Future<Output = Self> + Send;
+
+    /// Defines how inputs will be processed.
+    fn update(
+        &self,
+        message: Self::Input,
+        output: Sender<Self::Output>,
+    ) -> impl Future<Output = ()> + Send;
+}
+
+/// A worker that is ready to be started on the shared runtime.
+pub struct AsyncWorkerBuilder<W: AsyncWorker> {
+    concurrency: usize,
+    worker: PhantomData<fn() -> W>,
+}
+
+impl<W: AsyncWorker> Default for AsyncWorkerBuilder<W> {
+    fn default() -> Self {
+        Self {
+            concurrency: 1,
+            worker: PhantomData,
+        }
+    }
+}
+
+impl<W: AsyncWorker> Debug for AsyncWorkerBuilder<W> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("AsyncWorkerBuilder")
+            .field("concurrency", &self.concurrency)
+            .finish()
+    }
+}
+
+impl<W: AsyncWorker> AsyncWorkerBuilder<W> {
+    /// Sets how many inputs are processed at the same time.
+    ///
+    /// By default, inputs are processed one after another.
+    /// Values below one are treated as one.
+    #[must_use]
+    pub fn concurrency(mut self, limit: usize) -> Self {
+        self.concurrency = limit.max(1);
+        self
+    }
+
+    /// Starts the worker on the shared runtime.
+    ///
+    /// Each update runs as a separate task, so concurrent updates can run on
+    /// different threads of the runtime.
+    /// Updates that are still running when the worker is shut down are dropped.
+    pub fn detach(self, payload: W::Init) -> AsyncWorkerHandle<W> {
+        let Self { concurrency, .. } = self;
+
+        let (input_sender, input_receiver) = crate::channel::<W::Input>();
+        let RuntimeSenders {
+            output_sender,
+            output_receiver,
+            shutdown_notifier,
+            shutdown_recipient,
+            shutdown_on_drop,
+            mut shutdown_event,
+            ..
+        } = RuntimeSenders::<W::Output, ()>::new();
+
+        crate::spawn(async move {
+            let worker = Arc::new(W::init(payload).await);
+            let limit = Arc::new(Semaphore::new(concurrency));
+
+            let mut input = GuardedReceiver::new(input_receiver);
+            loop {
+                // Leave inputs in the channel until an update can start.
+                let permit = limit.clone().acquire_owned().fuse();
+                futures::pin_mut!(permit);
+                let permit = futures::select!(
+                    permit = permit => permit.expect("Semaphore of async worker closed"),
+                    _ = shutdown_event => break,
+                );
+
+                let message = futures::select!(
+                    message = input => message,
+                    _ = shutdown_event => break,
+                );
+
+                let worker = worker.clone();
+                let output = output_sender.clone();
+                let update = async move {
+                    worker.update(message, output).await;
+                    drop(permit);
+                };
+                crate::spawn(
+                    shutdown_recipient
+                        .clone()
+                        .register(update)
+                        .drop_on_shutdown(),
+                );
+            }
+
+            // Drops the updates that are still running.
+            shutdown_notifier.shutdown();
+        });
+
+        AsyncWorkerHandle {
+            sender: input_sender,
+            receiver: output_receiver,
+            shutdown_on_drop,
+        }
+    }
+}
+
+/// Handle to an async worker on the shared runtime.
+#[derive(Debug)]
+pub struct AsyncWorkerHandle<W: AsyncWorker> {
+    // Sends inputs to the worker.
+    sender: Sender<W::Input>,
+    // Where the worker will send its outputs to.
+    receiver: Receiver<W::Output>,
+    // Shutdown the worker when this is dropped
+    shutdown_on_drop: ShutdownOnDrop,
+}
+
+impl<W: AsyncWorker> AsyncWorkerHandle<W> {
+    /// Given a mutable closure, captures the receiver for handling.
+    pub fn connect_receiver<F: FnMut(&mut Sender<W::Input>, W::Output) + 'static>(
+        self,
+        mut func: F,
+    ) -> AsyncWorkerController<W> {
+        let Self {
+            sender,
+            receiver,
+            shutdown_on_drop,
+        } = self;
+
+        let mut sender_ = sender.clone();
+        crate::spawn_local(async move {
+            while let Some(event) = receiver.recv().await {
+                func(&mut sender_, event);
+            }
+        });
+
+        AsyncWorkerController {
+            sender,
+            shutdown_on_drop,
+        }
+    }
+
+    /// Forwards output events to the designated sender.
+    pub fn forward<X: 'static, F: (Fn(W::Output) -> X) + 'static>(
+        self,
+        sender: &Sender<X>,
+        transform: F,
+    ) -> AsyncWorkerController<W> {
+        let Self {
+            sender: own_sender,
+            receiver,
+            shutdown_on_drop,
+        } = self;
+
+        crate::spawn_local(receiver.forward(sender.clone(), transform));
+        AsyncWorkerController {
+            sender: own_sender,
+            shutdown_on_drop,
+        }
+    }
+
+    /// Ignore outputs from the worker and finish the builder.
+    #[must_use]
+    pub fn detach(self) -> AsyncWorkerController<W> {
+        let Self {
+            sender,
+            shutdown_on_drop,
+            ..
+        } = self;
+
+        AsyncWorkerController {
+            sender,
+            shutdown_on_drop,
+        }
+    }
+}
+
+/// Sends inputs to an async worker. On drop, shuts down the worker.
+#[derive(Debug)]
+pub struct AsyncWorkerController<W: AsyncWorker> {
+    // Sends inputs to the worker.
+    sender: Sender<W::Input>,
+    // Shutdown the worker when this is dropped
+    shutdown_on_drop: ShutdownOnDrop,
+}
+
+impl<W: AsyncWorker> AsyncWorkerController<W> {
+    /// Emits an input to the worker.
+    ///
+    /// **This method ignores errors.**
+    /// Only a log message will appear if the worker stopped.
+    pub fn emit(&self, event: W::Input) {
+        self.sender.emit(event);
+    }
+
+    /// Provides access to the worker's sender.
+    #[must_use]
+    pub const fn sender(&self) -> &Sender<W::Input> {
+        &self.sender
+    }
+
+    /// Dropping this type will usually stop the runtime of the worker.
+    /// With this method you can give the runtime a static lifetime.
+    /// In other words, dropping the [`AsyncWorkerController`] will not stop
+    /// the runtime anymore, it will run until the app is closed.
+    pub fn detach_runtime(&mut self) {
+        self.shutdown_on_drop.deactivate();
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::time::Duration;
+
+    use tokio::sync::Barrier;
+
+    use super::AsyncWorker;
+    use crate::Sender;
+
+    // Updates only finish if two of them run at the same time.
+    struct Pairs(Barrier);
+
+    impl AsyncWorker for Pairs {
+        type Init = ();
+        type Input = u8;
+        type Output = u8;
+
+        async fn init(_init: Self::Init) -> Self {
+            Pairs(Barrier::new(2))
+        }
+
+        async fn update(&self, message: Self::Input, output: Sender<Self::Output>) {
+            self.0.wait().await;
+            output.emit(message);
+        }
+    }
+
+    #[test]
+    fn concurrent_updates() {
+        let handle = Pairs::builder().concurrency(2).detach(());
+        handle.sender.emit(1);
+        handle.sender.emit(2);
+
+        let mut outputs = [
+            handle.receiver.recv_sync().unwrap(),
+            handle.receiver.recv_sync().unwrap(),
+        ];
+        outputs.sort_unstable();
+        assert_eq!(outputs, [1, 2]);
+    }
+
+    // Updates never finish and report when they are dropped.
+    struct Stall;
+
+    struct Dropped(Sender<u8>);
+
+    impl Drop for Dropped {
+        fn drop(&mut self) {
+            self.0.emit(0);
+        }
+    }
+
+    impl AsyncWorker for Stall {
+        type Init = ();
+        type Input = u8;
+        type Output = u8;
+
+        async fn init(_init: Self::Init) -> Self {
+            Stall
+        }
+
+        async fn update(&self, message: Self::Input, output: Sender<Self::Output>) {
+            let _dropped = Dropped(output.clone());
+            output.emit(message);
+            std::future::pending::<()>().await;
+        }
+    }
+
+    #[test]
+    fn drop_updates_on_shutdown() {
+        let handle = Stall::builder().detach(());
+        handle.sender.emit(1);
+        let timeout = Duration::from_secs(1);
+        assert_eq!(handle.receiver.0.recv_timeout(timeout), Ok(1));
+
+        drop(handle.shutdown_on_drop);
+        assert_eq!(handle.receiver.0.recv_timeout(timeout), Ok(0));
+    }
+}
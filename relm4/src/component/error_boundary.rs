@@ -0,0 +1,489 @@
+use std::any::{self, Any};
+use std::cell::{Ref, RefCell};
+use std::fmt::{self, Debug, Display};
+use std::panic::{self, AssertUnwindSafe};
+
+use gtk::prelude::{BoxExt, Cast};
+
+use crate::{
+    Component, ComponentBuilder, ComponentParts, ComponentSender, Receiver, Sender,
+    TryComponentSender,
+};
+
+/// A variant of [`Component`] whose initialization and updates can fail.
+///
+/// A `TryComponent` runs inside an [`ErrorBoundary`], which is launched like a regular
+/// component with [`TryComponent::builder()`].
+/// Once [`try_init`](Self::try_init) or an update returns an error, the boundary drops
+/// the model and widgets of the component, shows the [`fallback_view`](Self::fallback_view)
+/// instead and reports the error to the parent as [`BoundaryOutput::Failed`].
+/// Inputs that are sent afterwards are ignored.
+///
+/// If [`CATCH_PANICS`](Self::CATCH_PANICS) is set, panics are handled in the same way.
+pub trait TryComponent: Sized + 'static {
+    /// Messages which are received from commands executing in the background.
+    type CommandOutput: Debug + Send + 'static;
+
+    /// The message type that the component accepts as inputs.
+    type Input: Debug + 'static;
+
+    /// The message type that the component provides as outputs.
+    type Output: Debug + 'static;
+
+    /// The parameter used to initialize the component.
+    type Init;
+
+    /// The top-level widget of the component.
+    type Root: Debug + Clone + AsRef<gtk::Widget>;
+
+    /// The type that's used for storing widgets created for this component.
+    type Widgets: 'static;
+
+    /// The error returned if the component fails.
+    type Error: Debug + 'static;
+
+    /// Whether panics during initialization and updates are caught
+    /// and handled like errors.
+    ///
+    /// Catching panics relies on [`std::panic::catch_unwind`], so this
+    /// has no effect if the application is compiled with `panic = "abort"`.
+    const CATCH_PANICS: bool = false;
+
+    /// Create a builder for this component.
+    #[must_use]
+    fn builder() -> ComponentBuilder<ErrorBoundary<Self>> {
+        ErrorBoundary::builder()
+    }
+
+    /// Initializes the root widget.
+    fn init_root() -> Self::Root;
+
+    /// Creates the initial model and view, docking it into the component.
+    fn try_init(
+        init: Self::Init,
+        root: Self::Root,
+        sender: TryComponentSender<Self>,
+    ) -> Result<TryComponentParts<Self>, Self::Error>;
+
+    /// Processes inputs received by the component.
+    #[allow(unused)]
+    fn try_update(
+        &mut self,
+        message: Self::Input,
+        sender: TryComponentSender<Self>,
+        root: &Self::Root,
+    ) -> Result<(), Self::Error> {
+        Ok(())
+    }
+
+    /// Defines how the component should respond to command updates.
+    #[allow(unused)]
+    fn try_update_cmd(
+        &mut self,
+        message: Self::CommandOutput,
+        sender: TryComponentSender<Self>,
+        root: &Self::Root,
+    ) -> Result<(), Self::Error> {
+        Ok(())
+    }
+
+    /// Updates the view after the model has been updated.
+    #[allow(unused)]
+    fn update_view(&self, widgets: &mut Self::Widgets, sender: TryComponentSender<Self>) {}
+
+    /// Creates the widget that is shown instead of the component once it failed.
+    ///
+    /// The default implementation shows the error in a label.
+    fn fallback_view(error: &ComponentError<Self::Error>) -> gtk::Widget {
+        let label = gtk::Label::builder()
+            .label(error.to_string())
+            .wrap(true)
+            .css_classes(["error"])
+            .build();
+        label.upcast()
+    }
+
+    /// Last method called before a component is shut down.
+    ///
+    /// This method isn't called if the component failed before.
+    #[allow(unused)]
+    fn shutdown(&mut self, widgets: &mut Self::Widgets, output: Sender<Self::Output>) {}
+
+    /// An identifier for the component used for debug logging.
+    ///
+    /// The default implementation of this method uses the address of the component, but
+    /// implementations are free to provide more meaningful identifiers.
+    fn id(&self) -> String {
+        format!("{:p}", &self)
+    }
+}
+
+/// Contains the initial model and widgets being docked into a [`TryComponent`].
+#[derive(Debug)]
+pub struct TryComponentParts<C: TryComponent> {
+    /// The model of the component.
+    pub model: C,
+    /// The widgets created for the view.
+    pub widgets: C::Widgets,
+}
+
+/// The reason a [`TryComponent`] failed.
+#[derive(Debug)]
+pub enum ComponentError<E> {
+    /// The component returned an error.
+    Error(E),
+    /// The component panicked with the given message.
+    ///
+    /// Only reported if [`TryComponent::CATCH_PANICS`] is set.
+    Panic(String),
+}
+
+impl<E: Debug> Display for ComponentError<E> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Error(error) => write!(f, "{error:?}"),
+            Self::Panic(message) => write!(f, "panicked: {message}"),
+        }
+    }
+}
+
+/// The output of an [`ErrorBoundary`].
+#[derive(Debug)]
+pub enum BoundaryOutput<O, E> {
+    /// An output of the component.
+    Output(O),
+    /// The component failed and was replaced by its fallback view.
+    Failed(ComponentError<E>),
+}
+
+/// Runs a [`TryComponent`] and replaces it with a fallback view once it fails.
+///
+/// The root of the boundary is a [`gtk::Box`] that contains
+/// either the root of the component or the fallback view.
+pub struct ErrorBoundary<C: TryComponent> {
+    // `None` once the component failed.
+    component: RefCell<Option<C>>,
+    root: gtk::Box,
+    child_root: C::Root,
+    sender: TryComponentSender<C>,
+    output: Sender<C::Output>,
+    // Outputs of the component that weren't forwarded to the parent yet.
+    pending_outputs: Receiver<C::Output>,
+}
+
+impl<C: TryComponent> ErrorBoundary<C> {
+    /// Returns the model of the component or [`None`] if it failed.
+    #[must_use]
+    pub fn component(&self) -> Option<Ref<'_, C>> {
+        Ref::filter_map(self.component.borrow(), Option::as_ref).ok()
+    }
+
+    /// Returns [`true`] if the component failed.
+    #[must_use]
+    pub fn is_failed(&self) -> bool {
+        self.component.borrow().is_none()
+    }
+
+    /// Runs a fallible part of the component and catches panics if enabled.
+    fn guard<T>(f: impl FnOnce() -> Result<T, C::Error>) -> Result<T, ComponentError<C::Error>> {
+        if C::CATCH_PANICS {
+            match panic::catch_unwind(AssertUnwindSafe(f)) {
+                Ok(result) => result.map_err(ComponentError::Error),
+                Err(payload) => Err(ComponentError::Panic(panic_message(payload))),
+            }
+        } else {
+            f().map_err(ComponentError::Error)
+        }
+    }
+
+    /// Replaces the component with its fallback view and reports the error.
+    fn fail(&self, error: ComponentError<C::Error>, sender: &ComponentSender<Self>) {
+        tracing::error!("Component {} failed: {error}", any::type_name::<C>());
+
+        // Might panic again, so drop the model only after the view was replaced.
+        let component = self.component.borrow_mut().take();
+        self.root.remove(self.child_root.as_ref());
+        self.root.append(&C::fallback_view(&error));
+        drop(component);
+
+        // Forward earlier outputs first, so the parent receives them in order.
+        for output in self.pending_outputs.0.try_iter() {
+            sender.output(BoundaryOutput::Output(output)).ok();
+        }
+        sender.output(BoundaryOutput::Failed(error)).ok();
+    }
+
+    /// Runs an update of the component if it didn't fail yet.
+    fn try_update_with(
+        &mut self,
+        sender: &ComponentSender<Self>,
+        update: impl FnOnce(&mut C, TryComponentSender<C>, &C::Root) -> Result<(), C::Error>,
+    ) {
+        let Some(component) = self.component.get_mut() else {
+            tracing::debug!(
+                "Ignored message for failed component {}",
+                any::type_name::<C>()
+            );
+            return;
+        };
+
+        let child_sender = self.sender.clone();
+        let child_root = &self.child_root;
+        if let Err(error) = Self::guard(|| update(component, child_sender, child_root)) {
+            self.fail(error, sender);
+        }
+    }
+}
+
+impl<C: TryComponent> Debug for ErrorBoundary<C> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ErrorBoundary")
+            .field("component", &any::type_name::<C>())
+            .field("failed", &self.is_failed())
+            .finish_non_exhaustive()
+    }
+}
+
+impl<C: TryComponent> Component for ErrorBoundary<C> {
+    type CommandOutput = C::CommandOutput;
+    type Input = C::Input;
+    type Output = BoundaryOutput<C::Output, C::Error>;
+    type Init = C::Init;
+    type Root = gtk::Box;
+    // `None` once the component failed.
+    type Widgets = Option<C::Widgets>;
+
+    fn init_root() -> Self::Root {
+        gtk::Box::default()
+    }
+
+    fn init(
+        init: Self::Init,
+        root: Self::Root,
+        sender: ComponentSender<Self>,
+    ) -> ComponentParts<Self> {
+        let (output, output_receiver) = crate::channel();
+        let pending_outputs = Receiver(output_receiver.0.clone());
+        crate::spawn_local(
+            output_receiver.forward(sender.output_sender().clone(), BoundaryOutput::Output),
+        );
+
+        let child_root = C::init_root();
+        root.append(child_root.as_ref());
+        let child_sender = TryComponentSender::from_boundary(&sender, output.clone());
+
+        let mut model = Self {
+            component: RefCell::new(None),
+            root,
+            child_root: child_root.clone(),
+            sender: child_sender.clone(),
+            output,
+            pending_outputs,
+        };
+
+        let widgets = match Self::guard(|| C::try_init(init, child_root, child_sender)) {
+            Ok(parts) => {
+                *model.component.get_mut() = Some(parts.model);
+                Some(parts.widgets)
+            }
+            Err(error) => {
+                model.fail(error, &sender);
+                None
+            }
+        };
+
+        ComponentParts { model, widgets }
+    }
+
+    fn update(&mut self, message: Self::Input, sender: ComponentSender<Self>, _root: &Self::Root) {
+        self.try_update_with(&sender, |component, sender, root| {
+            component.try_update(message, sender, root)
+        });
+    }
+
+    fn update_cmd(
+        &mut self,
+        message: Self::CommandOutput,
+        sender: ComponentSender<Self>,
+        _root: &Self::Root,
+    ) {
+        self.try_update_with(&sender, |component, sender, root| {
+            component.try_update_cmd(message, sender, root)
+        });
+    }
+
+    fn update_view(&self, widgets: &mut Self::Widgets, sender: ComponentSender<Self>) {
+        let result = {
+            let component = self.component.borrow();
+            match (component.as_ref(), widgets.as_mut()) {
+                (Some(component), Some(widgets)) => Self::guard(|| {
+                    component.update_view(widgets, self.sender.clone());
+                    Ok(())
+                }),
+                _ => Ok(()),
+            }
+        };
+
+        if let Err(error) = result {
+            self.fail(error, &sender);
+        }
+        if self.is_failed() {
+            *widgets = None;
+        }
+    }
+
+    fn shutdown(&mut self, widgets: &mut Self::Widgets, _output: Sender<Self::Output>) {
+        if let (Some(component), Some(widgets)) = (self.component.get_mut(), widgets) {
+            component.shutdown(widgets, self.output.clone());
+        }
+    }
+
+    fn id(&self) -> String {
+        match &*self.component.borrow() {
+            Some(component) => component.id(),
+            None => format!("{:p}", &self),
+        }
+    }
+}
+
+/// Extracts the message of a caught panic.
+pub(crate) fn panic_message(payload: Box<dyn Any + Send>) -> String {
+    match payload.downcast::<String>() {
+        Ok(message) => *message,
+        Err(payload) => match payload.downcast::<&'static str>() {
+            Ok(message) => (*message).to_owned(),
+            Err(_) => "Box<dyn Any>".to_owned(),
+        },
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use gtk::prelude::{ObjectExt, WidgetExt};
+
+    use super::{BoundaryOutput, ComponentError, TryComponent, TryComponentParts};
+    use crate::testing::ComponentTester;
+    use crate::{ComponentController, TryComponentSender};
+
+    #[derive(Debug)]
+    enum Msg {
+        Increment,
+        Fail,
+        Panic,
+    }
+
+    #[derive(Debug)]
+    struct Counter(u8);
+
+    impl TryComponent for Counter {
+        type CommandOutput = ();
+        type Input = Msg;
+        type Output = u8;
+        type Init = bool;
+        type Root = gtk::Box;
+        type Widgets = ();
+        type Error = &'static str;
+
+        const CATCH_PANICS: bool = true;
+
+        fn init_root() -> Self::Root {
+            gtk::Box::default()
+        }
+
+        fn try_init(
+            fail: Self::Init,
+            _root: Self::Root,
+            _sender: TryComponentSender<Self>,
+        ) -> Result<TryComponentParts<Self>, Self::Error> {
+            if fail {
+                return Err("init failed");
+            }
+            Ok(TryComponentParts {
+                model: Counter(0),
+                widgets: (),
+            })
+        }
+
+        fn try_update(
+            &mut self,
+            message: Self::Input,
+            sender: TryComponentSender<Self>,
+            _root: &Self::Root,
+        ) -> Result<(), Self::Error> {
+            match message {
+                Msg::Increment => {
+                    self.0 += 1;
+                    sender.output(self.0).unwrap();
+                    Ok(())
+                }
+                Msg::Fail => Err("update failed"),
+                Msg::Panic => panic!("counter panicked"),
+            }
+        }
+    }
+
+    #[gtk::test]
+    fn fail_update() {
+        let mut tester = ComponentTester::from_builder(Counter::builder(), false);
+        tester.send(Msg::Increment);
+        tester.send(Msg::Fail);
+        tester.send(Msg::Increment);
+
+        assert!(tester.model().is_failed());
+        assert!(tester.model().component().is_none());
+        assert!(tester.widget().first_child().unwrap().is::<gtk::Label>());
+
+        let outputs = tester.shutdown();
+        assert!(matches!(
+            outputs[..],
+            [
+                BoundaryOutput::Output(1),
+                BoundaryOutput::Failed(ComponentError::Error("update failed")),
+            ]
+        ));
+    }
+
+    #[gtk::test]
+    fn keep_output_order() {
+        // Process both inputs at once, before earlier outputs are forwarded.
+        let mut tester = ComponentTester::from_builder(Counter::builder().batch_inputs(), false);
+        let sender = tester.controller().sender().clone();
+        sender.emit(Msg::Increment);
+        sender.emit(Msg::Fail);
+        tester.process();
+
+        let outputs = tester.shutdown();
+        assert!(matches!(
+            outputs[..],
+            [
+                BoundaryOutput::Output(1),
+                BoundaryOutput::Failed(ComponentError::Error("update failed")),
+            ]
+        ));
+    }
+
+    #[gtk::test]
+    fn fail_init() {
+        let tester = ComponentTester::from_builder(Counter::builder(), true);
+        assert!(tester.model().is_failed());
+
+        let outputs = tester.shutdown();
+        assert!(matches!(
+            outputs[..],
+            [BoundaryOutput::Failed(ComponentError::Error("init failed"))]
+        ));
+    }
+
+    #[gtk::test]
+    fn catch_panic() {
+        let mut tester = ComponentTester::from_builder(Counter::builder(), false);
+        tester.send(Msg::Panic);
+        assert!(tester.model().is_failed());
+
+        let outputs = tester.shutdown();
+        assert!(matches!(
+            &outputs[..],
+            [BoundaryOutput::Failed(ComponentError::Panic(message))] if message == "counter panicked"
+        ));
+    }
+}
@@ -0,0 +1,492 @@
+use std::cell::RefCell;
+use std::collections::VecDeque;
+use std::future::Future;
+use std::mem::{self, Discriminant};
+use std::pin::Pin;
+use std::rc::{Rc, Weak};
+use std::task::{Context, Poll};
+use std::time::{Duration, Instant};
+
+use futures::future::FusedFuture;
+
+use crate::clock::Sleep;
+use crate::GuardedReceiver;
+
+/// Decides when an input is passed to the update function of a component.
+///
+/// Set it for a component with
+/// [`ComponentBuilder::input_timing()`](crate::ComponentBuilder::input_timing).
+/// Inputs are grouped by their enum variant, so all inputs of the same
+/// variant should use the same timing.
+///
+/// ```
+/// # use std::time::Duration;
+/// # use relm4::component::InputTiming;
+/// enum Msg {
+///     Search(String),
+///     Resize(i32, i32),
+///     Submit,
+/// }
+///
+/// fn timing(msg: &Msg) -> InputTiming {
+///     match msg {
+///         Msg::Search(_) => InputTiming::Debounce(Duration::from_millis(300)),
+///         Msg::Resize(..) => InputTiming::Coalesce,
+///         Msg::Submit => InputTiming::Immediate,
+///     }
+/// }
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum InputTiming {
+    /// Pass the input to the component as usual.
+    #[default]
+    Immediate,
+    /// Skip the input if a newer input of the same variant is already queued.
+    Coalesce,
+    /// Wait until no input of the same variant was sent for the
+    /// given duration, then pass the latest one.
+    Debounce(Duration),
+    /// Pass at most one input of the same variant per duration.
+    ///
+    /// The first input is passed immediately.
+    /// Inputs sent in the meantime are coalesced and the latest one
+    /// is passed once the duration has passed.
+    Throttle(Duration),
+}
+
+/// Decides which inputs are processed first if several are queued.
+///
+/// Set it for a component with
+/// [`ComponentBuilder::input_priority()`](crate::ComponentBuilder::input_priority).
+/// Inputs of the same priority are processed in the order they were sent.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Default)]
+pub enum InputPriority {
+    /// Process the input only if no other inputs are queued.
+    Low,
+    /// Process the input in the order it was sent.
+    #[default]
+    Normal,
+    /// Process the input before all other queued inputs,
+    /// for example to cancel an operation or to close a window.
+    High,
+}
+
+/// An input that waits for its deadline.
+struct Delayed<T> {
+    variant: Discriminant<T>,
+    message: Option<T>,
+    deadline: Instant,
+    // Only set for throttled inputs.
+    period: Option<Duration>,
+}
+
+/// Holds the receiver of an [`InputQueue`], so a restarted component can take it over.
+///
+/// Only the queue holds a strong reference, so the channel is still closed once the queue is dropped.
+pub(crate) type InputSlot<T> = Weak<RefCell<Option<GuardedReceiver<'static, T>>>>;
+
+/// Receives inputs and applies their [`InputTiming`] and [`InputPriority`].
+///
+/// Without timing and priority functions, this behaves exactly like a [`GuardedReceiver`].
+///
+/// Inputs are only taken from a bounded channel while fewer inputs than its capacity
+/// are waiting in the queue, so the [`OverflowPolicy`](crate::OverflowPolicy)
+/// of the channel still applies if the component can't keep up.
+pub(crate) struct InputQueue<T: 'static> {
+    // `None` once the receiver was taken over.
+    receiver: Rc<RefCell<Option<GuardedReceiver<'static, T>>>>,
+    timing: fn(&T) -> InputTiming,
+    priority: fn(&T) -> InputPriority,
+    // Only `false` if neither timing nor priority are set.
+    queued: bool,
+    // The maximum number of inputs that wait in the buffer.
+    capacity: usize,
+    // Boxed, so the queue is `Unpin` even if the inputs aren't.
+    buffer: Box<Buffer<T>>,
+    timer: Option<(Instant, Sleep)>,
+}
+
+/// Inputs that were received, but not passed to the component yet.
+struct Buffer<T> {
+    backlog: VecDeque<T>,
+    delayed: Vec<Delayed<T>>,
+}
+
+impl<T> Buffer<T> {
+    fn len(&self) -> usize {
+        let delayed = self
+            .delayed
+            .iter()
+            .filter(|delayed| delayed.message.is_some());
+        self.backlog.len() + delayed.count()
+    }
+}
+
+impl<T: 'static> InputQueue<T> {
+    pub(crate) fn new(
+        receiver: GuardedReceiver<'static, T>,
+        timing: Option<fn(&T) -> InputTiming>,
+        priority: Option<fn(&T) -> InputPriority>,
+    ) -> Self {
+        Self {
+            capacity: receiver
+                .capacity()
+                .map_or(usize::MAX, |capacity| capacity.max(1)),
+            receiver: Rc::new(RefCell::new(Some(receiver))),
+            queued: timing.is_some() || priority.is_some(),
+            timing: timing.unwrap_or(|_| InputTiming::Immediate),
+            priority: priority.unwrap_or(|_| InputPriority::Normal),
+            buffer: Box::new(Buffer {
+                backlog: VecDeque::new(),
+                delayed: Vec::new(),
+            }),
+            timer: None,
+        }
+    }
+
+    /// Returns the number of inputs that are queued or wait for their timing.
+    pub(crate) fn len(&self) -> usize {
+        let received = self
+            .receiver
+            .borrow()
+            .as_ref()
+            .map_or(0, GuardedReceiver::len);
+        self.buffer.len() + received
+    }
+
+    /// Returns a slot to take over the receiver while the queue is alive.
+    pub(crate) fn slot(&self) -> InputSlot<T> {
+        Rc::downgrade(&self.receiver)
+    }
+
+    fn poll_receiver(&self, cx: &mut Context<'_>) -> Poll<T> {
+        match &mut *self.receiver.borrow_mut() {
+            Some(receiver) => Pin::new(receiver).poll(cx),
+            None => Poll::Pending,
+        }
+    }
+
+    fn push(&mut self, message: T) {
+        let variant = mem::discriminant(&message);
+        let now = crate::clock::now();
+        let delayed = self
+            .buffer
+            .delayed
+            .iter_mut()
+            .find(|delayed| delayed.variant == variant);
+
+        match ((self.timing)(&message), delayed) {
+            (InputTiming::Immediate | InputTiming::Coalesce, _) => self.enqueue(message),
+            (InputTiming::Debounce(duration), Some(delayed)) => {
+                delayed.message = Some(message);
+                delayed.deadline = now + duration;
+            }
+            (InputTiming::Debounce(duration), None) => self.buffer.delayed.push(Delayed {
+                variant,
+                message: Some(message),
+                deadline: now + duration,
+                period: None,
+            }),
+            (InputTiming::Throttle(_), Some(delayed)) => delayed.message = Some(message),
+            (InputTiming::Throttle(period), None) => {
+                self.enqueue(message);
+                self.buffer.delayed.push(Delayed {
+                    variant,
+                    message: None,
+                    deadline: now + period,
+                    period: Some(period),
+                });
+            }
+        }
+    }
+
+    /// Adds an input to the backlog behind all inputs with the same or a higher priority.
+    fn enqueue(&mut self, message: T) {
+        let priority = (self.priority)(&message);
+        let backlog = &mut self.buffer.backlog;
+        let position = backlog
+            .iter()
+            .position(|queued| (self.priority)(queued) < priority);
+        match position {
+            Some(position) => backlog.insert(position, message),
+            None => backlog.push_back(message),
+        }
+    }
+
+    /// Moves delayed inputs whose deadline passed to the backlog.
+    fn release(&mut self, now: Instant) {
+        let mut released = Vec::new();
+        self.buffer.delayed.retain_mut(|delayed| {
+            if delayed.deadline > now {
+                return true;
+            }
+
+            let message = delayed.message.take();
+            let is_released = message.is_some();
+            released.extend(message);
+
+            // Throttled inputs sent right after this one still have to wait.
+            match delayed.period {
+                Some(period) if is_released => {
+                    delayed.deadline = now + period;
+                    true
+                }
+                _ => false,
+            }
+        });
+
+        for message in released {
+            self.enqueue(message);
+        }
+    }
+}
+
+impl<T: 'static> Future for InputQueue<T> {
+    type Output = T;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let this = self.get_mut();
+        if !this.queued {
+            return this.poll_receiver(cx);
+        }
+
+        let now = crate::clock::now();
+        loop {
+            // Receive queued inputs to sort and coalesce them,
+            // but leave the rest in the channel once the buffer is full.
+            let mut full = false;
+            loop {
+                if this.buffer.len() >= this.capacity {
+                    full = true;
+                    break;
+                }
+                match this.poll_receiver(cx) {
+                    Poll::Ready(message) => this.push(message),
+                    Poll::Pending => break,
+                }
+            }
+
+            this.release(now);
+
+            while let Some(message) = this.buffer.backlog.pop_front() {
+                let variant = mem::discriminant(&message);
+                let outdated = (this.timing)(&message) == InputTiming::Coalesce
+                    && this
+                        .buffer
+                        .backlog
+                        .iter()
+                        .any(|queued| mem::discriminant(queued) == variant);
+                if !outdated {
+                    return Poll::Ready(message);
+                }
+            }
+
+            // Receive more inputs if only outdated inputs were dropped from a full buffer.
+            if !full || this.buffer.len() >= this.capacity {
+                break;
+            }
+        }
+
+        // Wake up once the next delayed input is due.
+        match this
+            .buffer
+            .delayed
+            .iter()
+            .map(|delayed| delayed.deadline)
+            .min()
+        {
+            Some(deadline) => {
+                let (_, timer) = match &mut this.timer {
+                    Some(timer) if timer.0 == deadline => timer,
+                    timer => timer.insert((
+                        deadline,
+                        crate::clock::sleep(deadline.saturating_duration_since(now)),
+                    )),
+                };
+                if timer.as_mut().poll(cx).is_ready() {
+                    this.timer = None;
+                    cx.waker().wake_by_ref();
+                }
+            }
+            None => this.timer = None,
+        }
+
+        Poll::Pending
+    }
+}
+
+impl<T: 'static> FusedFuture for InputQueue<T> {
+    fn is_terminated(&self) -> bool {
+        let terminated = self
+            .receiver
+            .borrow()
+            .as_ref()
+            .is_some_and(GuardedReceiver::is_terminated);
+        terminated && self.buffer.len() == 0
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::time::Duration;
+
+    use super::{InputPriority, InputTiming};
+    use crate::testing::ComponentTester;
+    use crate::{
+        Component, ComponentBuilder, ComponentController, ComponentParts, ComponentSender,
+        OverflowPolicy, SimpleComponent,
+    };
+
+    #[derive(Debug)]
+    enum Msg {
+        Resize(u8),
+        Search(&'static str),
+        Click,
+    }
+
+    #[derive(Default)]
+    struct Window {
+        updates: Vec<String>,
+    }
+
+    impl SimpleComponent for Window {
+        type Input = Msg;
+        type Output = ();
+        type Init = ();
+        type Root = ();
+        type Widgets = ();
+
+        fn init_root() -> Self::Root {}
+
+        fn init(
+            _init: Self::Init,
+            _root: Self::Root,
+            _sender: ComponentSender<Self>,
+        ) -> ComponentParts<Self> {
+            ComponentParts {
+                model: Window::default(),
+                widgets: (),
+            }
+        }
+
+        fn update(&mut self, message: Self::Input, _sender: ComponentSender<Self>) {
+            let update = match message {
+                Msg::Resize(size) => format!("resize {size}"),
+                Msg::Search(query) => format!("search {query}"),
+                Msg::Click => "click".into(),
+            };
+            self.updates.push(update);
+        }
+    }
+
+    fn timing(message: &Msg) -> InputTiming {
+        match message {
+            Msg::Resize(_) => InputTiming::Coalesce,
+            Msg::Search(_) => InputTiming::Debounce(Duration::from_millis(50)),
+            Msg::Click => InputTiming::Immediate,
+        }
+    }
+
+    #[gtk::test]
+    fn coalesce_and_debounce() {
+        let builder = Window::builder().input_timing(timing);
+        let mut tester = ComponentTester::from_builder(builder, ());
+        let sender = tester.controller().sender().clone();
+
+        sender.emit(Msg::Resize(1));
+        sender.emit(Msg::Search("r"));
+        sender.emit(Msg::Click);
+        sender.emit(Msg::Resize(2));
+        sender.emit(Msg::Search("relm"));
+        tester.process();
+        assert_eq!(tester.model().updates, ["click", "resize 2"]);
+
+        tester.advance(Duration::from_millis(49));
+        assert_eq!(tester.model().updates, ["click", "resize 2"]);
+        tester.advance(Duration::from_millis(1));
+        assert_eq!(tester.model().updates, ["click", "resize 2", "search relm"]);
+    }
+
+    fn priority(message: &Msg) -> InputPriority {
+        match message {
+            Msg::Resize(_) => InputPriority::Low,
+            Msg::Search(_) => InputPriority::Normal,
+            Msg::Click => InputPriority::High,
+        }
+    }
+
+    #[gtk::test]
+    fn prioritize() {
+        let builder = Window::builder().input_priority(priority);
+        let mut tester = ComponentTester::from_builder(builder, ());
+        let sender = tester.controller().sender().clone();
+
+        sender.emit(Msg::Resize(1));
+        sender.emit(Msg::Search("r"));
+        sender.emit(Msg::Click);
+        sender.emit(Msg::Search("relm"));
+        tester.process();
+        assert_eq!(
+            tester.model().updates,
+            ["click", "search r", "search relm", "resize 1"]
+        );
+    }
+
+    // Sends two inputs to itself for the inputs 0 and 1.
+    #[derive(Default)]
+    struct Relay {
+        updates: Vec<u8>,
+    }
+
+    impl SimpleComponent for Relay {
+        type Input = u8;
+        type Output = ();
+        type Init = ();
+        type Root = ();
+        type Widgets = ();
+
+        fn init_root() -> Self::Root {}
+
+        fn init(
+            _init: Self::Init,
+            _root: Self::Root,
+            _sender: ComponentSender<Self>,
+        ) -> ComponentParts<Self> {
+            ComponentParts {
+                model: Relay::default(),
+                widgets: (),
+            }
+        }
+
+        fn update(&mut self, message: Self::Input, sender: ComponentSender<Self>) {
+            self.updates.push(message);
+            if message < 2 {
+                sender.input(10 * (message + 1));
+                sender.input(10 * (message + 1) + 1);
+            }
+        }
+    }
+
+    fn assert_capacity(builder: ComponentBuilder<Relay>) {
+        let builder = builder.input_capacity(2, OverflowPolicy::DropNewest);
+        let mut tester = ComponentTester::from_builder(builder, ());
+        let sender = tester.controller().sender().clone();
+
+        sender.emit(0);
+        sender.emit(1);
+        tester.process();
+
+        // At most two inputs wait in the queue and two in the channel, so 21 is dropped.
+        assert_eq!(tester.model().updates, [0, 1, 10, 11, 20]);
+    }
+
+    #[gtk::test]
+    fn timing_keeps_capacity() {
+        assert_capacity(Relay::builder().input_timing(|_| InputTiming::Immediate));
+    }
+
+    #[gtk::test]
+    fn priority_keeps_capacity() {
+        assert_capacity(Relay::builder().input_priority(|_| InputPriority::Normal));
+    }
+}
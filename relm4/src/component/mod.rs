@@ -10,15 +10,46 @@ mod r#async;
 /// Message broker
 mod message_broker;
 
+/// Recording of component messages
+mod recorder;
+
+/// Time-travel debugging
+mod time_travel;
+
+/// Timing and priority of inputs
+mod input_timing;
+
+/// Components that can fail
+mod error_boundary;
+
+/// External event sources of components
+mod subscription;
+
 /// A simpler version of components that does work
 /// in the background.
 pub mod worker;
 
+/// Workers that run on the shared runtime
+mod async_worker;
+
+pub use async_worker::{AsyncWorker, AsyncWorkerBuilder, AsyncWorkerController, AsyncWorkerHandle};
+pub(crate) use error_boundary::panic_message;
+pub use error_boundary::{
+    BoundaryOutput, ComponentError, ErrorBoundary, TryComponent, TryComponentParts,
+};
+pub use input_timing::{InputPriority, InputTiming};
+pub(crate) use input_timing::{InputQueue, InputSlot};
 pub use message_broker::MessageBroker;
+pub use recorder::{ComponentMessage, MessageRecorder, RecordedMessage};
+pub use subscription::Subscription;
+pub(crate) use subscription::Subscriptions;
+pub use time_travel::TimeTravelDebugger;
 
+pub(crate) use sync::Redirect;
 pub use sync::{
-    CommandFuture, Component, ComponentBuilder, ComponentController, ComponentParts,
-    ComponentStream, Connector, Controller, SimpleComponent, StateWatcher,
+    Children, CommandFuture, Component, ComponentBuilder, ComponentController, ComponentParts,
+    ComponentStream, Connector, Controller, LazyConnector, LazyController, SimpleComponent,
+    StateWatcher,
 };
 
 pub use r#async::{
@@ -0,0 +1,243 @@
+use std::fmt::{self, Debug};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+use crate::Component;
+
+/// Records all messages a component processes or emits.
+///
+/// Attach a recorder to a component with [`ComponentBuilder::record()`](crate::ComponentBuilder::record).
+/// The recorded messages can be fed back into a fresh component with
+/// [`ComponentTester::replaying()`](crate::testing::ComponentTester::replaying)
+/// to reproduce bugs.
+/// With the `serde` feature enabled, recorded messages can also be written to and
+/// loaded from dump files, as long as the messages of the component implement
+/// `Serialize` and `Deserialize`.
+///
+/// Cloning this type returns a handle to the same recording.
+///
+/// ```
+/// use relm4::component::MessageRecorder;
+/// use relm4::{Component, ComponentController};
+/// # type MyComponent = ();
+///
+/// let recorder = MessageRecorder::new();
+/// let controller = MyComponent::builder()
+///     .record(&recorder)
+///     .launch(())
+///     .detach();
+///
+/// controller.emit(());
+///
+/// // Later, for example when an error occurred:
+/// let messages = recorder.messages();
+/// ```
+pub struct MessageRecorder<C: Component> {
+    inner: Arc<Mutex<RecorderInner<C>>>,
+    clone_input: fn(&C::Input) -> C::Input,
+    clone_command_output: fn(&C::CommandOutput) -> C::CommandOutput,
+    clone_output: fn(&C::Output) -> C::Output,
+}
+
+struct RecorderInner<C: Component> {
+    start: Instant,
+    messages: Vec<RecordedMessage<C>>,
+}
+
+impl<C> MessageRecorder<C>
+where
+    C: Component,
+    C::Input: Clone,
+    C::CommandOutput: Clone,
+    C::Output: Clone,
+{
+    /// Creates a new, empty recorder.
+    ///
+    /// All timestamps are relative to the creation of the recorder.
+    #[must_use]
+    pub fn new() -> Self {
+        Self {
+            inner: Arc::new(Mutex::new(RecorderInner {
+                start: Instant::now(),
+                messages: Vec::new(),
+            })),
+            clone_input: C::Input::clone,
+            clone_command_output: C::CommandOutput::clone,
+            clone_output: C::Output::clone,
+        }
+    }
+
+    /// Returns a copy of all messages recorded so far.
+    #[must_use]
+    pub fn messages(&self) -> Vec<RecordedMessage<C>> {
+        self.inner.lock().unwrap().messages.clone()
+    }
+}
+
+impl<C> Default for MessageRecorder<C>
+where
+    C: Component,
+    C::Input: Clone,
+    C::CommandOutput: Clone,
+    C::Output: Clone,
+{
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<C: Component> MessageRecorder<C> {
+    /// Removes and returns all messages recorded so far.
+    #[must_use]
+    pub fn take_messages(&self) -> Vec<RecordedMessage<C>> {
+        std::mem::take(&mut self.inner.lock().unwrap().messages)
+    }
+
+    /// Returns the number of recorded messages.
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.inner.lock().unwrap().messages.len()
+    }
+
+    /// Returns [`true`] if no messages were recorded.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    pub(crate) fn input(&self, id: String, message: &C::Input) {
+        self.record(id, ComponentMessage::Input((self.clone_input)(message)));
+    }
+
+    pub(crate) fn command_output(&self, id: String, message: &C::CommandOutput) {
+        self.record(
+            id,
+            ComponentMessage::CommandOutput((self.clone_command_output)(message)),
+        );
+    }
+
+    pub(crate) fn output(&self, id: String, message: &C::Output) {
+        self.record(id, ComponentMessage::Output((self.clone_output)(message)));
+    }
+
+    fn record(&self, id: String, message: ComponentMessage<C>) {
+        let mut inner = self.inner.lock().unwrap();
+        let time = inner.start.elapsed();
+        inner.messages.push(RecordedMessage { time, id, message });
+    }
+}
+
+impl<C: Component> Clone for MessageRecorder<C> {
+    fn clone(&self) -> Self {
+        Self {
+            inner: Arc::clone(&self.inner),
+            clone_input: self.clone_input,
+            clone_command_output: self.clone_command_output,
+            clone_output: self.clone_output,
+        }
+    }
+}
+
+impl<C: Component> Debug for MessageRecorder<C> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("MessageRecorder")
+            .field("messages", &self.inner.lock().unwrap().messages)
+            .finish()
+    }
+}
+
+/// A message that was recorded by a [`MessageRecorder`].
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(
+    feature = "serde",
+    serde(bound(
+        serialize = "C::Input: serde::Serialize, \
+                     C::CommandOutput: serde::Serialize, \
+                     C::Output: serde::Serialize",
+        deserialize = "C::Input: serde::Deserialize<'de>, \
+                       C::CommandOutput: serde::Deserialize<'de>, \
+                       C::Output: serde::Deserialize<'de>"
+    ))
+)]
+pub struct RecordedMessage<C: Component> {
+    /// The time since the recorder was created.
+    pub time: Duration,
+    /// The [`id()`](Component::id) of the component at the time of recording.
+    pub id: String,
+    /// The recorded message.
+    pub message: ComponentMessage<C>,
+}
+
+impl<C> Clone for RecordedMessage<C>
+where
+    C: Component,
+    C::Input: Clone,
+    C::CommandOutput: Clone,
+    C::Output: Clone,
+{
+    fn clone(&self) -> Self {
+        Self {
+            time: self.time,
+            id: self.id.clone(),
+            message: self.message.clone(),
+        }
+    }
+}
+
+impl<C: Component> Debug for RecordedMessage<C> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("RecordedMessage")
+            .field("time", &self.time)
+            .field("id", &self.id)
+            .field("message", &self.message)
+            .finish()
+    }
+}
+
+/// The messages that are processed or emitted by a component.
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(
+    feature = "serde",
+    serde(bound(
+        serialize = "C::Input: serde::Serialize, \
+                     C::CommandOutput: serde::Serialize, \
+                     C::Output: serde::Serialize",
+        deserialize = "C::Input: serde::Deserialize<'de>, \
+                       C::CommandOutput: serde::Deserialize<'de>, \
+                       C::Output: serde::Deserialize<'de>"
+    ))
+)]
+pub enum ComponentMessage<C: Component> {
+    /// An input that was processed by the component.
+    Input(C::Input),
+    /// A command output that was processed by the component.
+    CommandOutput(C::CommandOutput),
+    /// An output that was emitted by the component.
+    Output(C::Output),
+}
+
+impl<C> Clone for ComponentMessage<C>
+where
+    C: Component,
+    C::Input: Clone,
+    C::CommandOutput: Clone,
+    C::Output: Clone,
+{
+    fn clone(&self) -> Self {
+        match self {
+            Self::Input(message) => Self::Input(message.clone()),
+            Self::CommandOutput(message) => Self::CommandOutput(message.clone()),
+            Self::Output(message) => Self::Output(message.clone()),
+        }
+    }
+}
+
+impl<C: Component> Debug for ComponentMessage<C> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Input(message) => f.debug_tuple("Input").field(message).finish(),
+            Self::CommandOutput(message) => f.debug_tuple("CommandOutput").field(message).finish(),
+            Self::Output(message) => f.debug_tuple("Output").field(message).finish(),
+        }
+    }
+}
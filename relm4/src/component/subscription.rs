@@ -0,0 +1,305 @@
+use std::any::Any;
+use std::collections::hash_map::DefaultHasher;
+use std::collections::HashMap;
+use std::fmt;
+use std::hash::{Hash, Hasher};
+use std::time::Duration;
+
+use futures::{Stream, StreamExt};
+use gtk::glib;
+
+use crate::Sender;
+
+/// An external event source that sends inputs to a component.
+///
+/// Components return their subscriptions from
+/// [`Component::subscriptions()`](crate::Component::subscriptions).
+/// After each update, the runtime compares them with the running sources by ID.
+/// New sources are started, sources that aren't returned anymore are stopped
+/// and sources that are returned again keep running.
+/// Therefore, a source is only created once while it is active.
+///
+/// Subscriptions with equal IDs are treated as the same source,
+/// so the ID should include everything that configures the source.
+///
+/// ```
+/// # use std::time::Duration;
+/// # use relm4::component::Subscription;
+/// #[derive(Debug)]
+/// enum Msg {
+///     Tick,
+/// }
+///
+/// struct Clock {
+///     running: bool,
+///     period: Duration,
+/// }
+///
+/// impl Clock {
+///     fn subscriptions(&self) -> Vec<Subscription<Msg>> {
+///         if self.running {
+///             vec![Subscription::interval(("clock", self.period), self.period, || Msg::Tick)]
+///         } else {
+///             Vec::new()
+///         }
+///     }
+/// }
+/// ```
+pub struct Subscription<Msg> {
+    id: u64,
+    start: Box<dyn FnOnce(Sender<Msg>) -> Box<dyn Any>>,
+}
+
+impl<Msg: 'static> Subscription<Msg> {
+    /// Creates a subscription from a function that starts the source.
+    ///
+    /// The source sends its messages with the given sender.
+    /// Dropping the value returned by `start` stops the source.
+    pub fn new<G: 'static>(id: impl Hash, start: impl FnOnce(Sender<Msg>) -> G + 'static) -> Self {
+        Self {
+            id: hash(id),
+            start: Box::new(move |sender| Box::new(start(sender))),
+        }
+    }
+
+    /// Forwards the items of a stream on the shared tokio runtime.
+    ///
+    /// `stream` is only called when the source is started.
+    pub fn stream<S>(id: impl Hash, stream: impl FnOnce() -> S + 'static) -> Self
+    where
+        S: Stream<Item = Msg> + Send + 'static,
+        Msg: Send,
+    {
+        Self::new(id, move |sender| {
+            AbortTask(crate::spawn(forward(stream(), sender)))
+        })
+    }
+
+    /// Forwards the items of a stream on the main thread.
+    ///
+    /// Use this for sources that aren't [`Send`], like GLib signals.
+    /// `stream` is only called when the source is started.
+    pub fn local_stream<S>(id: impl Hash, stream: impl FnOnce() -> S + 'static) -> Self
+    where
+        S: Stream<Item = Msg> + 'static,
+    {
+        Self::new(id, move |sender| {
+            AbortLocal(crate::spawn_local(forward(stream(), sender)))
+        })
+    }
+
+    /// Sends a message every time the `period` has elapsed.
+    pub fn interval(id: impl Hash, period: Duration, message: impl Fn() -> Msg + 'static) -> Self {
+        Self::new(id, move |sender: Sender<Msg>| {
+            RemoveSource(Some(glib::timeout_add_local(period, move || {
+                sender.emit(message());
+                glib::ControlFlow::Continue
+            })))
+        })
+    }
+}
+
+impl<Msg> fmt::Debug for Subscription<Msg> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Subscription")
+            .field("id", &self.id)
+            .finish_non_exhaustive()
+    }
+}
+
+fn hash(id: impl Hash) -> u64 {
+    let mut hasher = DefaultHasher::new();
+    id.hash(&mut hasher);
+    hasher.finish()
+}
+
+async fn forward<Msg>(stream: impl Stream<Item = Msg>, sender: Sender<Msg>) {
+    futures::pin_mut!(stream);
+    while let Some(message) = stream.next().await {
+        if sender.send(message).is_err() {
+            return;
+        }
+    }
+}
+
+struct AbortTask(crate::JoinHandle<()>);
+
+impl Drop for AbortTask {
+    fn drop(&mut self) {
+        self.0.abort();
+    }
+}
+
+struct AbortLocal(glib::JoinHandle<()>);
+
+impl Drop for AbortLocal {
+    fn drop(&mut self) {
+        self.0.abort();
+    }
+}
+
+struct RemoveSource(Option<glib::SourceId>);
+
+impl Drop for RemoveSource {
+    fn drop(&mut self) {
+        if let Some(source) = self.0.take() {
+            source.remove();
+        }
+    }
+}
+
+/// The running subscriptions of a component.
+pub(crate) struct Subscriptions<Msg> {
+    sender: Sender<Msg>,
+    running: HashMap<u64, Box<dyn Any>>,
+}
+
+impl<Msg> Subscriptions<Msg> {
+    pub(crate) fn new(sender: Sender<Msg>) -> Self {
+        Self {
+            sender,
+            running: HashMap::new(),
+        }
+    }
+
+    /// Starts new sources and stops those that aren't requested anymore.
+    pub(crate) fn update(&mut self, subscriptions: Vec<Subscription<Msg>>) {
+        let mut running = HashMap::with_capacity(subscriptions.len());
+        for Subscription { id, start } in subscriptions {
+            if running.contains_key(&id) {
+                continue;
+            }
+            let source = self
+                .running
+                .remove(&id)
+                .unwrap_or_else(|| start(self.sender.clone()));
+            running.insert(id, source);
+        }
+
+        // Dropping the remaining sources stops them.
+        self.running = running;
+    }
+}
+
+impl<Msg> fmt::Debug for Subscriptions<Msg> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Subscriptions")
+            .field("sender", &self.sender)
+            .field("running", &self.running.len())
+            .finish()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::cell::Cell;
+    use std::rc::Rc;
+
+    use super::Subscription;
+    use crate::testing::ComponentTester;
+    use crate::{Component, ComponentParts, ComponentSender, SimpleComponent};
+
+    #[derive(Debug)]
+    enum Msg {
+        Toggle,
+        Value(u8),
+    }
+
+    struct Listener {
+        active: bool,
+        values: Vec<u8>,
+        // Number of running sources.
+        running: Rc<Cell<u8>>,
+    }
+
+    struct Running(Rc<Cell<u8>>);
+
+    impl Drop for Running {
+        fn drop(&mut self) {
+            self.0.set(self.0.get() - 1);
+        }
+    }
+
+    impl SimpleComponent for Listener {
+        type Input = Msg;
+        type Output = ();
+        type Init = Rc<Cell<u8>>;
+        type Root = ();
+        type Widgets = ();
+
+        fn init_root() -> Self::Root {}
+
+        fn init(
+            running: Self::Init,
+            _root: Self::Root,
+            _sender: ComponentSender<Self>,
+        ) -> ComponentParts<Self> {
+            ComponentParts {
+                model: Listener {
+                    active: false,
+                    values: Vec::new(),
+                    running,
+                },
+                widgets: (),
+            }
+        }
+
+        fn update(&mut self, message: Self::Input, _sender: ComponentSender<Self>) {
+            match message {
+                Msg::Toggle => self.active = !self.active,
+                Msg::Value(value) => self.values.push(value),
+            }
+        }
+
+        fn subscriptions(&self) -> Vec<Subscription<Self::Input>> {
+            if !self.active {
+                return Vec::new();
+            }
+
+            let running = self.running.clone();
+            vec![
+                Subscription::new("counter", move |_sender| {
+                    running.set(running.get() + 1);
+                    Running(running)
+                }),
+                Subscription::local_stream("values", || {
+                    futures::stream::iter([Msg::Value(1), Msg::Value(2)])
+                }),
+            ]
+        }
+    }
+
+    #[gtk::test]
+    fn start_and_stop() {
+        let running = Rc::new(Cell::new(0));
+        let mut tester = ComponentTester::<Listener>::new(running.clone());
+        assert_eq!(running.get(), 0);
+
+        tester.send(Msg::Toggle);
+        tester.process();
+        assert_eq!(running.get(), 1);
+        assert_eq!(tester.model().values, [1, 2]);
+
+        // Sources that are still requested keep running.
+        tester.send(Msg::Value(3));
+        tester.process();
+        assert_eq!(running.get(), 1);
+        assert_eq!(tester.model().values, [1, 2, 3]);
+
+        tester.send(Msg::Toggle);
+        tester.process();
+        assert_eq!(running.get(), 0);
+    }
+
+    #[gtk::test]
+    fn suppress_sources_while_replaying() {
+        let running = Rc::new(Cell::new(0));
+        let mut tester = ComponentTester::replaying(Listener::builder(), running.clone());
+
+        // Recordings already contain the inputs of the sources.
+        tester.send(Msg::Toggle);
+        tester.process();
+        assert_eq!(running.get(), 1);
+        assert!(tester.model().values.is_empty());
+    }
+}
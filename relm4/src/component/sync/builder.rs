@@ -2,18 +2,26 @@
 // Copyright 2022 System76 <info@system76.com>
 // SPDX-License-Identifier: MIT or Apache-2.0
 
-use super::super::MessageBroker;
+use super::super::{
+    InputPriority, InputQueue, InputTiming, MessageBroker, MessageRecorder, Subscriptions,
+    TimeTravelDebugger,
+};
 use super::{Component, ComponentParts, Connector, StateWatcher};
+use crate::executor::{self, CommandExecutor};
+use crate::inspector::{self, ComponentKind, Inspection};
 use crate::{
-    late_initialization, ComponentSender, GuardedReceiver, Receiver, RelmContainerExt,
-    RelmWidgetExt, RuntimeSenders, Sender,
+    late_initialization, CommandSender, ComponentSender, GuardedReceiver, OutputHook,
+    OverflowPolicy, Receiver, RelmContainerExt, RelmWidgetExt, RuntimeSenders, Sender,
 };
+use futures::FutureExt;
 use gtk::glib;
-use gtk::prelude::{GtkWindowExt, NativeDialogExt};
+use gtk::prelude::{GtkWindowExt, NativeDialogExt, WidgetExt};
 use std::any;
-use std::cell::RefCell;
+use std::cell::{Cell, RefCell};
+use std::fmt::Debug;
 use std::marker::PhantomData;
 use std::rc::Rc;
+use std::sync::{Arc, Mutex};
 use tracing::info_span;
 
 /// A component that is ready for docking and launch.
@@ -22,22 +30,75 @@ pub struct ComponentBuilder<C: Component> {
     /// The root widget of the component.
     pub root: C::Root,
     priority: glib::Priority,
+    input_capacity: Option<(usize, OverflowPolicy)>,
+    pub(crate) input_timing: Option<fn(&C::Input) -> InputTiming>,
+    pub(crate) input_priority: Option<fn(&C::Input) -> InputPriority>,
+    batch_inputs: bool,
+    pub(crate) executor: Option<Arc<dyn CommandExecutor>>,
+    recording: Option<Recording<C>>,
+    time_travel: Option<TimeTravelDebugger<C>>,
+    debug_model: Option<fn(&C) -> String>,
+    // Only set if view updates are suspended while the root is hidden.
+    suspend: Option<fn(&C::Root) -> &gtk::Widget>,
 
     pub(super) component: PhantomData<C>,
 }
 
+/// Replaces the senders the component uses to send messages to itself,
+/// so they can be intercepted before they reach the runtime.
+pub(crate) struct Redirect<C: Component> {
+    pub(crate) input: Option<Sender<C::Input>>,
+    pub(crate) command: Option<CommandSender<C::CommandOutput>>,
+    /// Used by restarted components to keep sending outputs to the same receiver.
+    pub(crate) output: Option<Sender<C::Output>>,
+}
+
+impl<C: Component> Default for Redirect<C> {
+    fn default() -> Self {
+        Self {
+            input: None,
+            command: None,
+            output: None,
+        }
+    }
+}
+
+/// Records the messages of a component.
+#[derive(Debug)]
+struct Recording<C: Component> {
+    recorder: MessageRecorder<C>,
+    /// The ID of the model when it processed the last message.
+    id: Arc<Mutex<String>>,
+    /// Records outputs where they are sent.
+    on_output: OutputHook<C::Output>,
+}
+
 impl<C: Component> Default for ComponentBuilder<C> {
     /// Prepares a component for initialization.
     fn default() -> Self {
+        Self::with_root(C::init_root())
+    }
+}
+
+impl<C: Component> ComponentBuilder<C> {
+    /// Prepares a component for initialization on an existing root widget.
+    pub(super) fn with_root(root: C::Root) -> Self {
         Self {
-            root: C::init_root(),
+            root,
             priority: glib::Priority::default(),
+            input_capacity: None,
+            input_timing: None,
+            input_priority: None,
+            batch_inputs: false,
+            executor: None,
+            recording: None,
+            time_travel: None,
+            debug_model: None,
+            suspend: None,
             component: PhantomData,
         }
     }
-}
 
-impl<C: Component> ComponentBuilder<C> {
     /// Configure the root widget before launching.
     #[must_use]
     pub fn update_root<F: FnOnce(&mut C::Root)>(mut self, func: F) -> Self {
@@ -61,6 +122,135 @@ impl<C: Component> ComponentBuilder<C> {
         self.priority = priority;
         self
     }
+
+    /// Limit the number of inputs that can be queued for this component.
+    ///
+    /// The `policy` decides what happens to inputs that are sent while
+    /// the queue is full, also for inputs sent with [`Sender::emit()`].
+    /// By default, the queue is unbounded.
+    ///
+    /// This has no effect on components launched with
+    /// [`launch_with_broker()`](Self::launch_with_broker).
+    ///
+    /// # Panics
+    ///
+    /// Panics if `capacity` is zero.
+    #[must_use]
+    pub fn input_capacity(mut self, capacity: usize, policy: OverflowPolicy) -> Self {
+        assert!(
+            capacity > 0,
+            "The input queue needs room for at least one input"
+        );
+        self.input_capacity = Some((capacity, policy));
+        self
+    }
+
+    /// Creates the channel for the inputs of the component.
+    pub(crate) fn input_channel(&self) -> (Sender<C::Input>, Receiver<C::Input>) {
+        match self.input_capacity {
+            Some((capacity, policy)) => crate::bounded_channel(capacity, policy),
+            None => crate::channel(),
+        }
+    }
+
+    /// Delay, coalesce or throttle inputs before they are passed to the component.
+    ///
+    /// The `timing` function is called for every input to decide
+    /// when it's processed, see [`InputTiming`].
+    ///
+    /// With an [`input_capacity()`](Self::input_capacity), at most that many inputs
+    /// wait for their timing while the others stay in the channel, so the
+    /// [`OverflowPolicy`] still applies.
+    #[must_use]
+    pub fn input_timing(mut self, timing: fn(&C::Input) -> InputTiming) -> Self {
+        self.input_timing = Some(timing);
+        self
+    }
+
+    /// Process important inputs before other queued inputs.
+    ///
+    /// The `priority` function is called for every input to decide
+    /// its [`InputPriority`].
+    /// Inputs are only reordered while they wait in the queue,
+    /// so this doesn't interrupt the processing of other inputs.
+    ///
+    /// With an [`input_capacity()`](Self::input_capacity), at most that many inputs
+    /// are reordered at once while the others stay in the channel, so the
+    /// [`OverflowPolicy`] still applies.
+    #[must_use]
+    pub fn input_priority(mut self, priority: fn(&C::Input) -> InputPriority) -> Self {
+        self.input_priority = Some(priority);
+        self
+    }
+
+    /// Process all queued inputs before updating the view.
+    ///
+    /// By default, [`update_with_view()`](Component::update_with_view) is called
+    /// for every input. In batch mode, [`update()`](Component::update) is called
+    /// for each input that is ready and [`update_view()`](Component::update_view)
+    /// only once afterwards, which avoids redundant view updates if many
+    /// inputs arrive at once.
+    /// Inputs that are sent while a batch is processed, for example by the
+    /// component itself, are part of the next batch.
+    ///
+    /// Custom implementations of [`update_with_view()`](Component::update_with_view)
+    /// aren't called for inputs in batch mode.
+    #[must_use]
+    pub fn batch_inputs(mut self) -> Self {
+        self.batch_inputs = true;
+        self
+    }
+
+    /// Run the commands of this component on a custom [`CommandExecutor`].
+    ///
+    /// By default, the executor of the current thread is used,
+    /// see [`set_thread_executor()`](crate::executor::set_thread_executor).
+    #[must_use]
+    pub fn command_executor(mut self, executor: impl CommandExecutor) -> Self {
+        self.executor = Some(Arc::new(executor));
+        self
+    }
+
+    /// Record all inputs, command outputs and outputs of this component.
+    ///
+    /// Only outputs sent with [`ComponentSender::output()`] are recorded,
+    /// not those sent directly with the [`output_sender()`](ComponentSender::output_sender).
+    ///
+    /// Workers started with [`detach_worker()`](Self::detach_worker) are not recorded.
+    #[must_use]
+    pub fn record(mut self, recorder: &MessageRecorder<C>) -> Self
+    where
+        C::Input: Send,
+        C::Output: Send,
+    {
+        let id = Arc::new(Mutex::new(String::new()));
+        let output_recorder = recorder.clone();
+        let output_id = id.clone();
+        self.recording = Some(Recording {
+            recorder: recorder.clone(),
+            id,
+            on_output: OutputHook::new(move |message| {
+                output_recorder.output(output_id.lock().unwrap().clone(), message);
+            }),
+        });
+        self
+    }
+
+    /// Keep a history of the model to step through it with a [`TimeTravelDebugger`].
+    #[must_use]
+    pub fn time_travel(mut self, debugger: &TimeTravelDebugger<C>) -> Self {
+        self.time_travel = Some(debugger.clone());
+        self
+    }
+}
+
+impl<C: Component + Debug> ComponentBuilder<C> {
+    /// Show a [`Debug`] view of the model in the [`inspector`](crate::inspector).
+    #[must_use]
+    pub fn inspect(mut self) -> Self {
+        self.debug_model = Some(inspector::debug_model);
+        self
+    }
 }
 
 impl<C: Component> ComponentBuilder<C>
@@ -74,6 +264,23 @@ where
 
         self
     }
+
+    /// Skip view updates while the root widget is unmapped.
+    ///
+    /// While the root widget is hidden, for example on a page of a [`gtk::Stack`]
+    /// that isn't visible, [`update()`](Component::update) and
+    /// [`update_cmd()`](Component::update_cmd) are still called for every message,
+    /// but [`update_view()`](Component::update_view) isn't.
+    /// Once the root widget is mapped again, the view is updated once.
+    ///
+    /// Custom implementations of [`update_with_view()`](Component::update_with_view)
+    /// and [`update_cmd_with_view()`](Component::update_cmd_with_view)
+    /// aren't called while the component is suspended.
+    #[must_use]
+    pub fn suspend_when_hidden(mut self) -> Self {
+        self.suspend = Some(AsRef::as_ref);
+        self
+    }
 }
 
 impl<C: Component> ComponentBuilder<C>
@@ -137,9 +344,24 @@ impl<C: Component> ComponentBuilder<C> {
     /// Starts the component, passing ownership to a future attached to a [gtk::glib::MainContext].
     pub fn launch(self, payload: C::Init) -> Connector<C> {
         // Used for all events to be processed by this component's internal service.
-        let (input_sender, input_receiver) = crate::channel::<C::Input>();
+        let (input_sender, input_receiver) = self.input_channel();
 
-        self.launch_with_input_channel(payload, input_sender, input_receiver)
+        self.launch_with_input_channel(payload, input_sender, input_receiver, Redirect::default())
+            .0
+    }
+
+    /// Similar to [`launch()`](ComponentBuilder::launch), but the messages the
+    /// component sends to itself are redirected.
+    ///
+    /// Returns the sender that passes command outputs to the runtime of the component.
+    pub(crate) fn launch_intercepted(
+        self,
+        payload: C::Init,
+        redirect: Redirect<C>,
+    ) -> (Connector<C>, CommandSender<C::CommandOutput>) {
+        let (input_sender, input_receiver) = self.input_channel();
+
+        self.launch_with_input_channel(payload, input_sender, input_receiver, redirect)
     }
 
     /// Similar to [`launch()`](ComponentBuilder::launch) but also initializes a [`MessageBroker`].
@@ -157,16 +379,47 @@ impl<C: Component> ComponentBuilder<C> {
             payload,
             input_sender,
             input_receiver.expect("Message broker launched multiple times"),
+            Redirect::default(),
         )
+        .0
     }
 
-    fn launch_with_input_channel(
+    pub(super) fn launch_with_input_channel(
         self,
         payload: C::Init,
         input_sender: Sender<C::Input>,
         input_receiver: Receiver<C::Input>,
-    ) -> Connector<C> {
-        let Self { root, priority, .. } = self;
+        redirect: Redirect<C>,
+    ) -> (Connector<C>, CommandSender<C::CommandOutput>) {
+        self.launch_with_guarded_receiver(
+            payload,
+            input_sender,
+            GuardedReceiver::new(input_receiver),
+            redirect,
+        )
+    }
+
+    /// Launches the component with an input channel that another runtime might have used before.
+    pub(super) fn launch_with_guarded_receiver(
+        self,
+        payload: C::Init,
+        input_sender: Sender<C::Input>,
+        input_receiver: GuardedReceiver<'static, C::Input>,
+        redirect: Redirect<C>,
+    ) -> (Connector<C>, CommandSender<C::CommandOutput>) {
+        let Self {
+            root,
+            priority,
+            executor,
+            recording,
+            time_travel,
+            debug_model,
+            input_timing,
+            input_priority,
+            batch_inputs,
+            suspend,
+            ..
+        } = self;
 
         let RuntimeSenders {
             output_sender,
@@ -178,16 +431,32 @@ impl<C: Component> ComponentBuilder<C> {
             shutdown_on_drop,
             mut shutdown_event,
         } = RuntimeSenders::<C::Output, C::CommandOutput>::new();
+        let output_sender = redirect.output.unwrap_or(output_sender);
 
         // Gets notifications when a component's model and view is updated externally.
         let (notifier, notifier_receiver) = crate::channel();
 
+        // Commands might be redirected, so keep a sender that reaches the runtime.
+        let runtime_cmd_sender = cmd_sender.clone();
+
+        // Outputs are recorded with the ID of the model from the last processed message.
+        let (recorder, on_output) = match recording {
+            Some(Recording {
+                recorder,
+                id,
+                on_output,
+            }) => (Some((recorder, id)), Some(on_output)),
+            None => (None, None),
+        };
+
         // Encapsulates the senders used by component methods.
-        let component_sender = ComponentSender::new(
-            input_sender.clone(),
+        let component_sender = ComponentSender::with_output_hook(
+            redirect.input.unwrap_or_else(|| input_sender.clone()),
             output_sender.clone(),
-            cmd_sender,
+            redirect.command.unwrap_or(cmd_sender),
             shutdown_recipient,
+            executor.unwrap_or_else(executor::thread_executor),
+            on_output,
         );
 
         // Constructs the initial model and view with the initial payload.
@@ -196,22 +465,60 @@ impl<C: Component> ComponentBuilder<C> {
             root.clone(),
             component_sender.clone(),
         )));
-        let watcher = StateWatcher {
+        let mut input = InputQueue::new(input_receiver, input_timing, input_priority);
+        let mut watcher = StateWatcher {
             state,
             notifier,
             shutdown_on_drop,
+            runtime: None,
+            input: input.slot(),
+            output: output_sender.0.downgrade(),
         };
 
+        if let Some((_, id)) = &recorder {
+            *id.lock().unwrap() = watcher.state.borrow().model.id();
+        }
+
+        if let Some(debugger) = &time_travel {
+            debugger.attach(&watcher.state, watcher.notifier.clone());
+            debugger.record(&watcher.state.borrow().model);
+        }
+
+        let inspection =
+            Inspection::register(ComponentKind::Component, &input_sender, C::id, debug_model);
+        if let Some(inspection) = &inspection {
+            inspection.update(&watcher.state.borrow().model);
+        }
+
+        // Set if view updates were skipped while the root widget was hidden.
+        let stale = Rc::new(Cell::new(false));
+        if let Some(widget) = suspend {
+            let stale = stale.clone();
+            // Don't keep the runtime alive.
+            let notifier = watcher.notifier.0.downgrade();
+            widget(&root).connect_map(move |_| {
+                if let (true, Some(notifier)) = (stale.get(), notifier.upgrade()) {
+                    notifier.send(()).ok();
+                }
+            });
+        }
+        let is_suspended =
+            move |root: &C::Root| suspend.is_some_and(|widget| !widget(root).is_mapped());
+
+        // Sources of inputs that are started and stopped after each update.
+        // They send inputs like the component itself, so they are redirected as well.
+        let mut subscriptions = Subscriptions::new(component_sender.input_sender().clone());
+        subscriptions.update(watcher.state.borrow().model.subscriptions());
+
         let rt_state = watcher.state.clone();
         let rt_root = root.clone();
 
         // Spawns the component's service. It will receive both `Self::Input` and
         // `Self::CommandOutput` messages. It will spawn commands as requested by
         // updates, and send `Self::Output` messages externally.
-        crate::spawn_local_with_priority(priority, async move {
+        let runtime = crate::spawn_local_with_priority(priority, async move {
             let mut notifier = GuardedReceiver::new(notifier_receiver);
-            let mut cmd = GuardedReceiver::new(cmd_receiver);
-            let mut input = GuardedReceiver::new(input_receiver);
+            let mut cmd = cmd_receiver;
             loop {
                 futures::select!(
                     // Performs the model update, checking if the update requested a command.
@@ -230,7 +537,44 @@ impl<C: Component> ComponentBuilder<C> {
                         );
                         let _enter = span.enter();
 
-                        model.update_with_view(widgets, message, component_sender.clone(), &rt_root);
+                        let suspended = is_suspended(&rt_root);
+                        // Inputs that are sent during the batch wait for the next one,
+                        // so the main loop isn't blocked by components that keep sending inputs.
+                        let mut remaining = if batch_inputs { input.len() } else { 0 };
+                        let mut next = Some(message);
+                        while let Some(message) = next.take() {
+                            if let Some((recorder, recorded_id)) = &recorder {
+                                let id = model.id();
+                                recorder.input(id.clone(), &message);
+                                *recorded_id.lock().unwrap() = id;
+                            }
+
+                            if batch_inputs || suspended {
+                                model.update(message, component_sender.clone(), &rt_root);
+                                if remaining > 0 {
+                                    remaining -= 1;
+                                    next = (&mut input).now_or_never();
+                                }
+                            } else {
+                                model.update_with_view(widgets, message, component_sender.clone(), &rt_root);
+                            }
+
+                            if let Some(debugger) = &time_travel {
+                                debugger.record(model);
+                            }
+                        }
+
+                        if suspended {
+                            stale.set(true);
+                        } else if batch_inputs {
+                            // Update the view once after all queued inputs were processed.
+                            model.update_view(widgets, component_sender.clone());
+                        }
+                        subscriptions.update(model.subscriptions());
+
+                        if let Some(inspection) = &inspection {
+                            inspection.update(model);
+                        }
                     }
 
                     // Handles responses from a command.
@@ -248,7 +592,27 @@ impl<C: Component> ComponentBuilder<C> {
                         );
                         let _enter = span.enter();
 
-                        model.update_cmd_with_view(widgets, message, component_sender.clone(), &rt_root);
+                        if let Some((recorder, recorded_id)) = &recorder {
+                            let id = model.id();
+                            recorder.command_output(id.clone(), &message);
+                            *recorded_id.lock().unwrap() = id;
+                        }
+
+                        if is_suspended(&rt_root) {
+                            model.update_cmd(message, component_sender.clone(), &rt_root);
+                            stale.set(true);
+                        } else {
+                            model.update_cmd_with_view(widgets, message, component_sender.clone(), &rt_root);
+                        }
+                        subscriptions.update(model.subscriptions());
+
+                        if let Some(debugger) = &time_travel {
+                            debugger.record(model);
+                        }
+
+                        if let Some(inspection) = &inspection {
+                            inspection.update(model);
+                        }
                     }
 
                     // Triggered when the model and view have been updated externally.
@@ -258,7 +622,17 @@ impl<C: Component> ComponentBuilder<C> {
                             widgets,
                         } = &mut *rt_state.borrow_mut();
 
-                        model.update_view(widgets, component_sender.clone());
+                        if is_suspended(&rt_root) {
+                            stale.set(true);
+                        } else {
+                            stale.set(false);
+                            model.update_view(widgets, component_sender.clone());
+                        }
+                        subscriptions.update(model.subscriptions());
+
+                        if let Some(inspection) = &inspection {
+                            inspection.update(model);
+                        }
                     }
 
                     // Triggered when the component is destroyed
@@ -278,12 +652,122 @@ impl<C: Component> ComponentBuilder<C> {
             }
         });
 
+        watcher.runtime = Some(runtime);
+
         // Give back a type for controlling the component service.
-        Connector {
+        let connector = Connector {
             state: watcher,
             widget: root,
             sender: input_sender,
             receiver: output_receiver,
+        };
+
+        (connector, runtime_cmd_sender)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::cell::Cell;
+
+    use gtk::glib;
+    use gtk::prelude::{GtkWindowExt, WidgetExt};
+
+    use crate::testing::fixtures::{show, ViewCounter};
+    use crate::testing::ComponentTester;
+    use crate::{Component, ComponentController, ComponentParts, ComponentSender, SimpleComponent};
+
+    #[gtk::test]
+    fn batch_inputs() {
+        let mut tester = ComponentTester::from_builder(ViewCounter::builder().batch_inputs(), ());
+        let sender = tester.controller().sender().clone();
+
+        for _ in 0..5 {
+            sender.emit(());
         }
+        tester.process();
+
+        assert_eq!(tester.model().value, 5);
+        assert_eq!(tester.model().views.get(), 1);
+    }
+
+    // Sends itself the next number until it reaches five.
+    #[derive(Default)]
+    struct Echo {
+        value: u8,
+        views: Cell<u8>,
+    }
+
+    impl SimpleComponent for Echo {
+        type Input = u8;
+        type Output = ();
+        type Init = ();
+        type Root = ();
+        type Widgets = ();
+
+        fn init_root() -> Self::Root {}
+
+        fn init(
+            _init: Self::Init,
+            _root: Self::Root,
+            _sender: ComponentSender<Self>,
+        ) -> ComponentParts<Self> {
+            ComponentParts {
+                model: Echo::default(),
+                widgets: (),
+            }
+        }
+
+        fn update(&mut self, message: Self::Input, sender: ComponentSender<Self>) {
+            self.value = message;
+            if message < 5 {
+                sender.input(message + 1);
+            }
+        }
+
+        fn update_view(&self, _widgets: &mut Self::Widgets, _sender: ComponentSender<Self>) {
+            self.views.set(self.views.get() + 1);
+        }
+    }
+
+    #[gtk::test]
+    fn batch_ends_with_queued_inputs() {
+        let mut tester = ComponentTester::from_builder(Echo::builder().batch_inputs(), ());
+        let sender = tester.controller().sender().clone();
+
+        sender.emit(1);
+        let context = glib::MainContext::default();
+        context.iteration(false);
+        // The input sent by the component is processed in the next batch.
+        assert_eq!(tester.model().value, 1);
+        assert_eq!(tester.model().views.get(), 1);
+
+        tester.process();
+        assert_eq!(tester.model().value, 5);
+        assert_eq!(tester.model().views.get(), 5);
+    }
+
+    #[gtk::test]
+    fn suspend_when_hidden() {
+        let mut tester =
+            ComponentTester::from_builder(ViewCounter::builder().suspend_when_hidden(), ());
+        let window = show(tester.widget());
+
+        tester.send(());
+        assert_eq!(tester.model().views.get(), 1);
+
+        // Only the model is updated while the root is hidden.
+        tester.widget().set_visible(false);
+        tester.send(());
+        tester.send(());
+        assert_eq!(tester.model().value, 3);
+        assert_eq!(tester.model().views.get(), 1);
+
+        // The view is updated once it's visible again.
+        tester.widget().set_visible(true);
+        tester.process();
+        assert_eq!(tester.model().views.get(), 2);
+
+        window.destroy();
     }
 }
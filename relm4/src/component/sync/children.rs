@@ -0,0 +1,302 @@
+use std::any::Any;
+use std::borrow::Borrow;
+use std::collections::hash_map::Entry;
+use std::collections::HashMap;
+use std::fmt::{self, Debug};
+use std::hash::Hash;
+
+use super::{Component, ComponentBuilder, ComponentController, Controller};
+use crate::Sender;
+
+/// A collection of child components of different types, stored by key.
+///
+/// Outputs of every child are forwarded to the input sender of the parent
+/// with the mapping function passed to [`launch()`](Self::launch).
+/// Removing a child or dropping the collection shuts the children down.
+///
+/// ```
+/// # use relm4::prelude::*;
+/// # use relm4::Children;
+/// # struct Child;
+/// # impl SimpleComponent for Child {
+/// #     type Input = ();
+/// #     type Output = String;
+/// #     type Init = ();
+/// #     type Root = gtk::Box;
+/// #     type Widgets = ();
+/// #     fn init_root() -> Self::Root { gtk::Box::default() }
+/// #     fn init(_: (), _: gtk::Box, _: ComponentSender<Self>) -> ComponentParts<Self> {
+/// #         ComponentParts { model: Child, widgets: () }
+/// #     }
+/// # }
+/// #[derive(Debug)]
+/// enum Msg {
+///     Renamed(String),
+/// }
+///
+/// fn launch_children(children: &mut Children<&'static str, Msg>) {
+///     children.launch("editor", Child::builder(), (), Msg::Renamed);
+///     children.emit::<Child, _>(&"editor", ());
+/// }
+/// ```
+pub struct Children<K, I> {
+    sender: Sender<I>,
+    // Each value is a `Controller` of the component the child was launched as.
+    children: HashMap<K, Box<dyn Any>>,
+}
+
+impl<K, I> Children<K, I>
+where
+    K: Hash + Eq,
+    I: 'static,
+{
+    /// Creates an empty collection that forwards outputs of the children to `sender`.
+    #[must_use]
+    pub fn new(sender: &Sender<I>) -> Self {
+        Self {
+            sender: sender.clone(),
+            children: HashMap::new(),
+        }
+    }
+
+    /// Launches a child component and stores it with the given key.
+    ///
+    /// Outputs of the child are converted with `forward` and sent to the parent.
+    /// If a child with the same key exists, it is shut down and replaced.
+    pub fn launch<C, F>(
+        &mut self,
+        key: K,
+        builder: ComponentBuilder<C>,
+        init: C::Init,
+        forward: F,
+    ) -> &Controller<C>
+    where
+        C: Component,
+        F: Fn(C::Output) -> I + 'static,
+    {
+        let controller = builder.launch(init).forward(&self.sender, forward);
+        let child: Box<dyn Any> = Box::new(controller);
+        let child = match self.children.entry(key) {
+            Entry::Occupied(mut entry) => {
+                entry.insert(child);
+                entry.into_mut()
+            }
+            Entry::Vacant(entry) => entry.insert(child),
+        };
+        child
+            .downcast_ref()
+            .expect("The child was just inserted with this type")
+    }
+
+    /// Returns the controller of the child with the given key.
+    ///
+    /// Returns [`None`] if there's no such child or it isn't of type `C`.
+    #[must_use]
+    pub fn get<C, Q>(&self, key: &Q) -> Option<&Controller<C>>
+    where
+        C: Component,
+        K: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        self.children.get(key)?.downcast_ref()
+    }
+
+    /// Returns the controller of the child with the given key mutably.
+    ///
+    /// Returns [`None`] if there's no such child or it isn't of type `C`.
+    #[must_use]
+    pub fn get_mut<C, Q>(&mut self, key: &Q) -> Option<&mut Controller<C>>
+    where
+        C: Component,
+        K: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        self.children.get_mut(key)?.downcast_mut()
+    }
+
+    /// Sends an input to the child with the given key.
+    ///
+    /// **This method ignores errors.**
+    /// Only a log message will appear if there's no child of type `C` with this key.
+    pub fn emit<C, Q>(&self, key: &Q, message: C::Input)
+    where
+        C: Component,
+        K: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        if let Some(child) = self.get::<C, Q>(key) {
+            child.sender().emit(message);
+        } else {
+            tracing::warn!(
+                "No child component of type `{}` with this key",
+                std::any::type_name::<C>()
+            );
+        }
+    }
+
+    /// Shuts the child with the given key down and removes it.
+    ///
+    /// Returns [`true`] if a child was removed.
+    /// The root widget of the child has to be removed from its parent widget separately.
+    pub fn remove<Q>(&mut self, key: &Q) -> bool
+    where
+        K: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        self.children.remove(key).is_some()
+    }
+
+    /// Removes the child with the given key and returns its controller.
+    ///
+    /// Returns [`None`] and keeps the child if it isn't of type `C`.
+    pub fn take<C, Q>(&mut self, key: &Q) -> Option<Controller<C>>
+    where
+        C: Component,
+        K: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        if !self.children.get(key)?.is::<Controller<C>>() {
+            return None;
+        }
+        self.children
+            .remove(key)
+            .and_then(|child| child.downcast().ok())
+            .map(|child| *child)
+    }
+
+    /// Returns [`true`] if there's a child with the given key.
+    #[must_use]
+    pub fn contains_key<Q>(&self, key: &Q) -> bool
+    where
+        K: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        self.children.contains_key(key)
+    }
+
+    /// Returns an iterator over the keys of all children.
+    pub fn keys(&self) -> impl Iterator<Item = &K> {
+        self.children.keys()
+    }
+
+    /// Returns the number of children.
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.children.len()
+    }
+
+    /// Returns [`true`] if there are no children.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.children.is_empty()
+    }
+
+    /// Shuts all children down and removes them.
+    pub fn clear(&mut self) {
+        self.children.clear();
+    }
+}
+
+impl<K: Debug, I> Debug for Children<K, I> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Children")
+            .field("sender", &self.sender)
+            .field("keys", &self.children.keys().collect::<Vec<_>>())
+            .finish()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use gtk::glib;
+
+    use super::Children;
+    use crate::{Component, ComponentController, ComponentParts, ComponentSender, SimpleComponent};
+
+    #[derive(Debug, PartialEq, Eq)]
+    enum Msg {
+        Counted(u8),
+        Named(String),
+    }
+
+    struct Counter(u8);
+
+    impl SimpleComponent for Counter {
+        type Input = ();
+        type Output = u8;
+        type Init = u8;
+        type Root = ();
+        type Widgets = ();
+
+        fn init_root() -> Self::Root {}
+
+        fn init(
+            value: Self::Init,
+            _root: Self::Root,
+            _sender: ComponentSender<Self>,
+        ) -> ComponentParts<Self> {
+            ComponentParts {
+                model: Counter(value),
+                widgets: (),
+            }
+        }
+
+        fn update(&mut self, _message: Self::Input, sender: ComponentSender<Self>) {
+            self.0 += 1;
+            sender.output(self.0).unwrap();
+        }
+    }
+
+    struct Name;
+
+    impl SimpleComponent for Name {
+        type Input = String;
+        type Output = String;
+        type Init = ();
+        type Root = ();
+        type Widgets = ();
+
+        fn init_root() -> Self::Root {}
+
+        fn init(
+            _init: Self::Init,
+            _root: Self::Root,
+            _sender: ComponentSender<Self>,
+        ) -> ComponentParts<Self> {
+            ComponentParts {
+                model: Name,
+                widgets: (),
+            }
+        }
+
+        fn update(&mut self, message: Self::Input, sender: ComponentSender<Self>) {
+            sender.output(message).unwrap();
+        }
+    }
+
+    #[gtk::test]
+    fn launch_and_forward() {
+        let context = glib::MainContext::default();
+        let (sender, receiver) = crate::channel();
+        let mut children = Children::new(&sender);
+
+        children.launch("counter", Counter::builder(), 1, Msg::Counted);
+        children.launch("name", Name::builder(), (), Msg::Named);
+        assert_eq!(children.len(), 2);
+
+        children.emit::<Counter, _>("counter", ());
+        children.emit::<Name, _>("name", "relm4".into());
+        while context.iteration(false) {}
+        assert_eq!(
+            receiver.0.try_iter().collect::<Vec<_>>(),
+            [Msg::Counted(2), Msg::Named("relm4".into())]
+        );
+
+        assert!(children.get::<Name, _>("counter").is_none());
+        assert_eq!(children.get::<Counter, _>("counter").unwrap().model().0, 2);
+        assert!(children.take::<Name, _>("counter").is_none());
+        assert!(children.remove("counter"));
+        assert!(!children.contains_key("counter"));
+        assert_eq!(children.keys().collect::<Vec<_>>(), [&"name"]);
+    }
+}
@@ -5,9 +5,10 @@
 use std::cell::Ref;
 use std::fmt::{self, Debug};
 
-use crate::Sender;
+use crate::{RelmRemoveAllExt, Reply, Sender};
 
-use super::{Component, StateWatcher};
+use super::state_watcher::Channels;
+use super::{Component, ComponentBuilder, Connector, Redirect, StateWatcher};
 
 /// Shared behavior of component controller types.
 pub trait ComponentController<C: Component> {
@@ -19,6 +20,26 @@ pub trait ComponentController<C: Component> {
     /// Provides access to the component's sender.
     fn sender(&self) -> &Sender<C::Input>;
 
+    /// Sends a request to the component and calls `callback` with the reply.
+    ///
+    /// The callback is called on the main thread once the component answered.
+    /// If the component drops the [`Reply`] without answering, the callback isn't called.
+    /// Use [`Sender::request()`] to await the reply instead.
+    fn request<R: 'static>(
+        &self,
+        message: impl FnOnce(Reply<R>) -> C::Input,
+        callback: impl FnOnce(R) + 'static,
+    ) where
+        Self: Sized,
+    {
+        let response = self.sender().request(message);
+        crate::spawn_local(async move {
+            if let Some(reply) = response.await {
+                callback(reply);
+            }
+        });
+    }
+
     /// Provides access to the state of a component.
     fn state(&self) -> &StateWatcher<C>;
 
@@ -74,6 +95,67 @@ impl<C: Component> ComponentController<C> for Controller<C> {
     }
 }
 
+impl<C> Controller<C>
+where
+    C: Component,
+    C::Root: RelmRemoveAllExt,
+{
+    /// Shuts the component down and launches it again on the same root widget.
+    ///
+    /// `init` creates the parameter for the new component from the current model,
+    /// so state can be carried over.
+    /// The component keeps its input and output channels, so existing senders
+    /// and forwarded outputs keep working.
+    /// If the runtime of the component already stopped, for example because the
+    /// application is shutting down, the new component gets new channels instead.
+    ///
+    /// [`Component::shutdown()`] is called before all children of the root
+    /// widget are removed and the new component is initialized.
+    /// The new component is launched with the default [`ComponentBuilder`] settings.
+    pub fn restart(&mut self, init: impl FnOnce(&C) -> C::Init) {
+        let init = init(&self.model());
+        let channels = self.shutdown_now();
+
+        let builder = ComponentBuilder::<C>::with_root(self.widget.clone());
+        let connector = match channels {
+            Some(Channels { input, output }) => {
+                let redirect = Redirect {
+                    output: Some(output),
+                    ..Redirect::default()
+                };
+                builder
+                    .launch_with_guarded_receiver(init, self.sender.clone(), input, redirect)
+                    .0
+            }
+            None => builder.launch(init),
+        };
+        self.state = connector.state;
+        self.sender = connector.sender;
+    }
+
+    /// Shuts the component down and launches another component on the same root widget.
+    ///
+    /// `init` creates the parameter for the new component from the current model,
+    /// so state can be carried over.
+    /// Like [`restart()`](Self::restart), this removes all children of the root widget
+    /// after [`Component::shutdown()`] was called.
+    /// Outputs of the new component have to be handled again with the returned [`Connector`].
+    pub fn replace_with<C2>(mut self, init: impl FnOnce(&C) -> C2::Init) -> Connector<C2>
+    where
+        C2: Component<Root = C::Root>,
+    {
+        let init = init(&self.model());
+        self.shutdown_now();
+        ComponentBuilder::<C2>::with_root(self.widget.clone()).launch(init)
+    }
+
+    fn shutdown_now(&mut self) -> Option<Channels<C>> {
+        let channels = self.state.shutdown_now();
+        self.widget.remove_all();
+        channels
+    }
+}
+
 impl<C> Debug for Controller<C>
 where
     C: Component + Debug,
@@ -87,3 +169,133 @@ where
             .finish()
     }
 }
+
+#[cfg(test)]
+mod test {
+    use gtk::glib;
+    use gtk::prelude::{BoxExt, Cast, ObjectExt, WidgetExt};
+
+    use crate::{
+        Component, ComponentController, ComponentParts, ComponentSender, RelmIterChildrenExt,
+        SimpleComponent,
+    };
+
+    struct Counter(u8);
+
+    impl SimpleComponent for Counter {
+        type Input = ();
+        type Output = u8;
+        type Init = u8;
+        type Root = gtk::Box;
+        type Widgets = ();
+
+        fn init_root() -> Self::Root {
+            gtk::Box::default()
+        }
+
+        fn init(
+            value: Self::Init,
+            root: Self::Root,
+            _sender: ComponentSender<Self>,
+        ) -> ComponentParts<Self> {
+            root.append(&gtk::Label::new(Some(&value.to_string())));
+            ComponentParts {
+                model: Counter(value),
+                widgets: (),
+            }
+        }
+
+        fn update(&mut self, _message: Self::Input, _sender: ComponentSender<Self>) {
+            self.0 += 1;
+        }
+
+        fn shutdown(&mut self, _widgets: &mut Self::Widgets, output: crate::Sender<Self::Output>) {
+            output.emit(self.0);
+        }
+    }
+
+    struct Placeholder;
+
+    impl SimpleComponent for Placeholder {
+        type Input = ();
+        type Output = ();
+        type Init = ();
+        type Root = gtk::Box;
+        type Widgets = ();
+
+        fn init_root() -> Self::Root {
+            gtk::Box::default()
+        }
+
+        fn init(
+            _init: Self::Init,
+            root: Self::Root,
+            _sender: ComponentSender<Self>,
+        ) -> ComponentParts<Self> {
+            root.append(&gtk::Spinner::default());
+            ComponentParts {
+                model: Placeholder,
+                widgets: (),
+            }
+        }
+    }
+
+    fn labels(root: &gtk::Box) -> Vec<String> {
+        root.iter_children()
+            .filter_map(|child| child.downcast::<gtk::Label>().ok())
+            .map(|label| label.label().into())
+            .collect()
+    }
+
+    #[gtk::test]
+    fn restart_and_replace() {
+        let context = glib::MainContext::default();
+        let (sender, receiver) = crate::channel();
+        let mut controller = Counter::builder()
+            .launch(1)
+            .forward(&sender, |output| output);
+        let input = controller.sender().clone();
+
+        input.emit(());
+        while context.iteration(false) {}
+        controller.restart(|counter| counter.0 * 10);
+        while context.iteration(false) {}
+        assert_eq!(receiver.0.try_recv(), Ok(2));
+        assert_eq!(labels(controller.widget()), ["20"]);
+
+        // Existing senders reach the new component.
+        input.emit(());
+        while context.iteration(false) {}
+        assert_eq!(controller.model().0, 21);
+
+        let root = controller.widget().clone();
+        let placeholder = controller.replace_with::<Placeholder>(|_| ()).detach();
+        assert_eq!(receiver.0.try_recv(), Ok(21));
+        assert!(labels(&root).is_empty());
+        assert!(root.first_child().unwrap().is::<gtk::Spinner>());
+        assert_eq!(placeholder.widget(), &root);
+    }
+
+    #[gtk::test]
+    fn close_channels_with_runtime() {
+        let context = glib::MainContext::default();
+        let (sender, receiver) = crate::channel();
+        let mut controller = Counter::builder()
+            .launch(1)
+            .forward(&sender, |output| output);
+        drop(sender);
+
+        // Stop the runtime while the controller is alive.
+        controller.state.runtime.take().unwrap().abort();
+        while context.iteration(false) {}
+        assert!(controller.sender().send(()).is_err());
+        assert!(receiver.0.try_recv().is_err());
+        assert!(receiver.0.is_disconnected());
+
+        // Restarting needs new channels now.
+        controller.restart(|counter| counter.0 * 10);
+        controller.emit(());
+        while context.iteration(false) {}
+        assert_eq!(controller.model().0, 11);
+    }
+}
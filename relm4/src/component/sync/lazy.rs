@@ -0,0 +1,343 @@
+use std::cell::{Cell, OnceCell};
+use std::fmt::{self, Debug};
+use std::iter;
+use std::rc::{Rc, Weak};
+
+use gtk::glib;
+use gtk::prelude::{ObjectExt, WidgetExt};
+
+use super::{Component, ComponentBuilder, Controller, Redirect};
+use crate::{Receiver, Sender};
+
+impl<C> ComponentBuilder<C>
+where
+    C: Component,
+    C::Root: AsRef<gtk::Widget>,
+{
+    /// Prepares the component, but only launches it once it's needed.
+    ///
+    /// The component is launched the first time its root widget is mapped
+    /// or an input is sent to it. Inputs are buffered until then.
+    /// This is useful for pages of a [`gtk::Stack`] that aren't visible on startup.
+    pub fn launch_lazy(self, payload: C::Init) -> LazyConnector<C> {
+        let (input_sender, input_receiver) = self.input_channel();
+        let (output_sender, output_receiver) = crate::channel();
+        let root = self.root.clone();
+
+        let shared = Rc::new(Shared {
+            pending: Cell::new(Some(Pending {
+                builder: self,
+                payload,
+                input: Receiver(input_receiver.0.clone()),
+                output: output_sender,
+            })),
+            sender: input_sender.clone(),
+            waiter: Cell::new(None),
+            map_handler: Cell::new(None),
+            controller: OnceCell::new(),
+        });
+
+        let weak_shared = Rc::downgrade(&shared);
+        let waiter = crate::spawn_local(wait_for_input(weak_shared.clone(), input_receiver));
+        shared.waiter.set(Some(waiter));
+
+        let map_handler = root.as_ref().connect_map(move |_| {
+            if let Some(shared) = weak_shared.upgrade() {
+                shared.launch();
+            }
+        });
+        shared
+            .map_handler
+            .set(Some((root.as_ref().clone(), map_handler)));
+        if root.as_ref().is_mapped() {
+            shared.launch();
+        }
+
+        LazyConnector {
+            controller: LazyController {
+                shared,
+                root,
+                sender: input_sender,
+            },
+            receiver: output_receiver,
+        }
+    }
+}
+
+/// Launches the component once the first input arrives.
+async fn wait_for_input<C: Component>(shared: Weak<Shared<C>>, input: Receiver<C::Input>) {
+    let Some(first) = input.recv().await else {
+        return;
+    };
+    let Some(shared) = shared.upgrade() else {
+        return;
+    };
+
+    // Put the input back in front of the inputs that arrived in the meantime.
+    let queued: Vec<_> = input.0.drain().collect();
+    for message in iter::once(first).chain(queued) {
+        shared.sender.emit(message);
+    }
+
+    // This task is about to finish, so it doesn't need to be aborted.
+    drop(shared.waiter.take());
+    shared.launch();
+}
+
+struct Pending<C: Component> {
+    builder: ComponentBuilder<C>,
+    payload: C::Init,
+    input: Receiver<C::Input>,
+    output: Sender<C::Output>,
+}
+
+struct Shared<C: Component> {
+    // `None` once the component was launched.
+    pending: Cell<Option<Pending<C>>>,
+    sender: Sender<C::Input>,
+    waiter: Cell<Option<glib::JoinHandle<()>>>,
+    // Only set until the component was launched.
+    map_handler: Cell<Option<(gtk::Widget, glib::SignalHandlerId)>>,
+    controller: OnceCell<Controller<C>>,
+}
+
+impl<C: Component> Shared<C> {
+    fn launch(&self) {
+        let Some(pending) = self.pending.take() else {
+            return;
+        };
+
+        // The waiter would otherwise take inputs from the component.
+        if let Some(waiter) = self.waiter.take() {
+            waiter.abort();
+        }
+        self.disconnect_map();
+
+        let Pending {
+            builder,
+            payload,
+            input,
+            output,
+        } = pending;
+        let redirect = Redirect {
+            output: Some(output),
+            ..Redirect::default()
+        };
+        let (connector, _) =
+            builder.launch_with_input_channel(payload, self.sender.clone(), input, redirect);
+
+        let controller = Controller {
+            state: connector.state,
+            widget: connector.widget,
+            sender: connector.sender,
+        };
+        if self.controller.set(controller).is_err() {
+            unreachable!("Lazy component launched twice");
+        }
+    }
+
+    fn disconnect_map(&self) {
+        if let Some((root, map_handler)) = self.map_handler.take() {
+            root.disconnect(map_handler);
+        }
+    }
+}
+
+impl<C: Component> Drop for Shared<C> {
+    fn drop(&mut self) {
+        if let Some(waiter) = self.waiter.take() {
+            waiter.abort();
+        }
+        self.disconnect_map();
+    }
+}
+
+/// Contains the input sender and output receiver of a lazily launched component.
+///
+/// Created by [`ComponentBuilder::launch_lazy()`].
+/// Similar to [`Connector`](super::Connector), the output receiver is handled
+/// by choosing one of its methods.
+pub struct LazyConnector<C: Component> {
+    controller: LazyController<C>,
+    receiver: Receiver<C::Output>,
+}
+
+impl<C: Component> LazyConnector<C> {
+    /// Forwards output events to the designated sender.
+    pub fn forward<X: 'static, F: (Fn(C::Output) -> X) + 'static>(
+        self,
+        sender: &Sender<X>,
+        transform: F,
+    ) -> LazyController<C> {
+        let Self {
+            controller,
+            receiver,
+        } = self;
+
+        crate::spawn_local(receiver.forward(sender.clone(), transform));
+        controller
+    }
+
+    /// Ignore outputs from the component and finish the builder.
+    pub fn detach(self) -> LazyController<C> {
+        self.controller
+    }
+}
+
+impl<C: Component> Debug for LazyConnector<C> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("LazyConnector")
+            .field("controller", &self.controller)
+            .field("receiver", &self.receiver)
+            .finish()
+    }
+}
+
+/// Controls a component that is launched once it's needed.
+///
+/// Created by [`ComponentBuilder::launch_lazy()`].
+/// Dropping this type shuts the component down or,
+/// if it wasn't launched yet, drops the buffered inputs.
+pub struct LazyController<C: Component> {
+    shared: Rc<Shared<C>>,
+    root: C::Root,
+    sender: Sender<C::Input>,
+}
+
+impl<C: Component> LazyController<C> {
+    /// Emits an input to the component.
+    ///
+    /// This launches the component if it wasn't launched yet.
+    pub fn emit(&self, event: C::Input) {
+        self.sender.emit(event);
+        self.launch();
+    }
+
+    /// Provides access to the component's sender.
+    ///
+    /// Inputs sent with this sender launch the component once the
+    /// main loop processes them.
+    #[must_use]
+    pub const fn sender(&self) -> &Sender<C::Input> {
+        &self.sender
+    }
+
+    /// Returns the root widget of the component.
+    ///
+    /// The root widget exists before the component is launched,
+    /// so it can already be added to a container.
+    #[must_use]
+    pub const fn widget(&self) -> &C::Root {
+        &self.root
+    }
+
+    /// Launches the component if it wasn't launched yet.
+    pub fn launch(&self) {
+        self.shared.launch();
+    }
+
+    /// Returns [`true`] if the component was launched.
+    #[must_use]
+    pub fn is_launched(&self) -> bool {
+        self.shared.controller.get().is_some()
+    }
+
+    /// Returns the controller of the component if it was launched.
+    #[must_use]
+    pub fn controller(&self) -> Option<&Controller<C>> {
+        self.shared.controller.get()
+    }
+}
+
+impl<C: Component> Debug for LazyController<C> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("LazyController")
+            .field("root", &self.root)
+            .field("sender", &self.sender)
+            .field("launched", &self.is_launched())
+            .finish()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use gtk::glib;
+    use gtk::prelude::{GtkWindowExt, WidgetExt};
+
+    use crate::{Component, ComponentController, ComponentParts, ComponentSender, SimpleComponent};
+
+    struct Page(Vec<u8>);
+
+    impl SimpleComponent for Page {
+        type Input = u8;
+        type Output = u8;
+        type Init = ();
+        type Root = gtk::Box;
+        type Widgets = ();
+
+        fn init_root() -> Self::Root {
+            gtk::Box::default()
+        }
+
+        fn init(
+            _init: Self::Init,
+            _root: Self::Root,
+            _sender: ComponentSender<Self>,
+        ) -> ComponentParts<Self> {
+            ComponentParts {
+                model: Page(Vec::new()),
+                widgets: (),
+            }
+        }
+
+        fn update(&mut self, message: Self::Input, sender: ComponentSender<Self>) {
+            self.0.push(message);
+            sender.output(message).unwrap();
+        }
+    }
+
+    fn process() {
+        let context = glib::MainContext::default();
+        while context.iteration(false) {}
+    }
+
+    #[gtk::test]
+    fn launch_on_input() {
+        let (sender, receiver) = crate::channel();
+        let lazy = Page::builder()
+            .launch_lazy(())
+            .forward(&sender, |output| output);
+        process();
+        assert!(!lazy.is_launched());
+
+        let input = lazy.sender().clone();
+        input.emit(1);
+        input.emit(2);
+        process();
+
+        assert!(lazy.is_launched());
+        assert_eq!(lazy.controller().unwrap().model().0, [1, 2]);
+        assert_eq!(receiver.0.try_iter().collect::<Vec<_>>(), [1, 2]);
+
+        lazy.emit(3);
+        process();
+        assert_eq!(lazy.controller().unwrap().model().0, [1, 2, 3]);
+    }
+
+    #[gtk::test]
+    fn launch_on_map() {
+        let lazy = Page::builder().launch_lazy(()).detach();
+        process();
+        assert!(!lazy.is_launched());
+
+        let window = gtk::Window::builder().child(lazy.widget()).build();
+        window.present();
+        process();
+        assert!(lazy.widget().is_mapped());
+        assert!(lazy.is_launched());
+        // Later maps don't need to launch the component again.
+        assert!(lazy.shared.map_handler.take().is_none());
+
+        window.destroy();
+    }
+}
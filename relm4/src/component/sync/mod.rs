@@ -3,15 +3,20 @@
 // SPDX-License-Identifier: MIT or Apache-2.0
 
 mod builder;
+mod children;
 mod connector;
 mod controller;
+mod lazy;
 mod state_watcher;
 mod stream;
 mod traits;
 
 pub use builder::ComponentBuilder;
+pub(crate) use builder::Redirect;
+pub use children::Children;
 pub use connector::Connector;
 pub use controller::{ComponentController, Controller};
+pub use lazy::{LazyConnector, LazyController};
 pub use state_watcher::StateWatcher;
 pub use stream::ComponentStream;
 pub use traits::{Component, SimpleComponent};
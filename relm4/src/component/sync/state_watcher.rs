@@ -1,4 +1,7 @@
-use crate::{Component, ComponentParts, ShutdownOnDrop};
+use super::super::InputSlot;
+use crate::{Component, ComponentParts, GuardedReceiver, Sender, ShutdownOnDrop};
+
+use gtk::glib;
 
 use std::cell::{Ref, RefCell, RefMut};
 use std::fmt::{self, Debug};
@@ -10,8 +13,19 @@ use std::rc::Rc;
 pub struct StateWatcher<C: Component> {
     /// The models and widgets maintained by the component.
     pub(super) state: Rc<RefCell<ComponentParts<C>>>,
-    pub(super) notifier: crate::Sender<()>,
+    pub(super) notifier: Sender<()>,
     pub(super) shutdown_on_drop: ShutdownOnDrop,
+    /// Stops the runtime if the component is restarted.
+    pub(super) runtime: Option<glib::JoinHandle<()>>,
+    /// Lets a restarted component reuse the channels without keeping them open.
+    pub(super) input: InputSlot<C::Input>,
+    pub(super) output: flume::WeakSender<C::Output>,
+}
+
+/// The channels of a component that was shut down, so they can be passed to a new runtime.
+pub(super) struct Channels<C: Component> {
+    pub(super) input: GuardedReceiver<'static, C::Input>,
+    pub(super) output: Sender<C::Output>,
 }
 
 impl<C: Component> StateWatcher<C> {
@@ -31,6 +45,31 @@ impl<C: Component> StateWatcher<C> {
     pub(super) fn detach_runtime(&mut self) {
         self.shutdown_on_drop.deactivate()
     }
+
+    /// Stops the runtime and shuts the component down immediately.
+    ///
+    /// Returns the input receiver and output sender of the runtime,
+    /// unless the runtime stopped before.
+    pub(super) fn shutdown_now(&mut self) -> Option<Channels<C>> {
+        // Take the channels while the runtime still keeps them open.
+        let input = self
+            .input
+            .upgrade()
+            .and_then(|receiver| receiver.borrow_mut().take());
+        let output = self.output.upgrade().map(Sender::from);
+
+        self.shutdown_on_drop.deactivate();
+        if let Some(runtime) = self.runtime.take() {
+            runtime.abort();
+        }
+
+        let ComponentParts { model, widgets } = &mut *self.state.borrow_mut();
+        let shutdown_output = output.clone().unwrap_or_else(|| crate::channel().0);
+        model.shutdown(widgets, shutdown_output);
+
+        let (input, output) = input.zip(output)?;
+        Some(Channels { input, output })
+    }
 }
 
 impl<C> Debug for StateWatcher<C>
@@ -43,6 +82,7 @@ where
             .field("state", &self.state)
             .field("notifier", &self.notifier)
             .field("shutdown_on_drop", &self.shutdown_on_drop)
-            .finish()
+            .field("runtime", &self.runtime)
+            .finish_non_exhaustive()
     }
 }
@@ -4,6 +4,7 @@
 
 use std::fmt::Debug;
 
+use crate::component::Subscription;
 use crate::{ComponentBuilder, ComponentParts, ComponentSender, Sender};
 
 /// The fundamental building block of a Relm4 application.
@@ -116,6 +117,15 @@ pub trait Component: Sized + 'static {
         self.update_view(widgets, sender);
     }
 
+    /// Returns the external event sources the component currently listens to.
+    ///
+    /// This method is called after initialization and after each update.
+    /// The runtime starts new subscriptions and stops those that aren't returned anymore.
+    /// See [`Subscription`] for details.
+    fn subscriptions(&self) -> Vec<Subscription<Self::Input>> {
+        Vec::new()
+    }
+
     /// Last method called before a component is shut down.
     ///
     /// This method is guaranteed to be called even when the entire application is shut down.
@@ -170,6 +180,13 @@ pub trait SimpleComponent: Sized + 'static {
     #[allow(unused)]
     fn update_view(&self, widgets: &mut Self::Widgets, sender: ComponentSender<Self>) {}
 
+    /// Returns the external event sources the component currently listens to.
+    ///
+    /// See [`Component::subscriptions()`] for details.
+    fn subscriptions(&self) -> Vec<Subscription<Self::Input>> {
+        Vec::new()
+    }
+
     /// Last method called before a component is shut down.
     ///
     /// This method is guaranteed to be called even when the entire application is shut down.
@@ -209,6 +226,10 @@ where
         C::update_view(self, widgets, sender);
     }
 
+    fn subscriptions(&self) -> Vec<Subscription<Self::Input>> {
+        C::subscriptions(self)
+    }
+
     fn shutdown(&mut self, widgets: &mut Self::Widgets, output: Sender<Self::Output>) {
         self.shutdown(widgets, output);
     }
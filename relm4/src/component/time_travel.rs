@@ -0,0 +1,250 @@
+use std::cell::{Ref, RefCell};
+use std::fmt::{self, Debug};
+use std::rc::{Rc, Weak};
+
+use crate::{Component, ComponentParts, Sender};
+
+/// Keeps a history of the model of a component and allows
+/// stepping backwards and forwards through it.
+///
+/// Attach it to a component with [`ComponentBuilder::time_travel()`](crate::ComponentBuilder::time_travel).
+/// A snapshot of the model is taken after initialization and after every input
+/// or command output the component processes.
+///
+/// When you step through the history, the model of the component is replaced
+/// by a copy of the snapshot and the view is updated accordingly.
+/// If the component processes a new message afterwards, all snapshots after
+/// the current position are discarded and the history continues from there.
+///
+/// Changes made through [`StateWatcher::get_mut()`](crate::component::StateWatcher::get_mut)
+/// are not recorded.
+///
+/// Cloning this type returns a handle to the same history.
+pub struct TimeTravelDebugger<C: Component> {
+    inner: Rc<RefCell<TimeTravelInner<C>>>,
+    clone_model: fn(&C) -> C,
+}
+
+struct TimeTravelInner<C: Component> {
+    history: Vec<C>,
+    position: usize,
+    limit: Option<usize>,
+    state: Weak<RefCell<ComponentParts<C>>>,
+    notifier: Option<Sender<()>>,
+}
+
+impl<C: Component + Clone> TimeTravelDebugger<C> {
+    /// Creates a debugger that keeps every snapshot.
+    #[must_use]
+    pub fn new() -> Self {
+        Self::new_with_limit(None)
+    }
+
+    /// Creates a debugger that only keeps the latest `limit` snapshots.
+    #[must_use]
+    pub fn with_limit(limit: usize) -> Self {
+        Self::new_with_limit(Some(limit.max(1)))
+    }
+
+    fn new_with_limit(limit: Option<usize>) -> Self {
+        Self {
+            inner: Rc::new(RefCell::new(TimeTravelInner {
+                history: Vec::new(),
+                position: 0,
+                limit,
+                state: Weak::new(),
+                notifier: None,
+            })),
+            clone_model: C::clone,
+        }
+    }
+}
+
+impl<C: Component + Clone> Default for TimeTravelDebugger<C> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<C: Component> TimeTravelDebugger<C> {
+    /// Returns the number of snapshots in the history.
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.inner.borrow().history.len()
+    }
+
+    /// Returns [`true`] if no snapshots were taken yet.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Returns the position of the snapshot the component currently shows.
+    #[must_use]
+    pub fn position(&self) -> usize {
+        self.inner.borrow().position
+    }
+
+    /// Returns the snapshot at the given position.
+    #[must_use]
+    pub fn snapshot(&self, position: usize) -> Option<Ref<'_, C>> {
+        Ref::filter_map(self.inner.borrow(), |inner| inner.history.get(position)).ok()
+    }
+
+    /// Restores the previous snapshot.
+    ///
+    /// Returns [`false`] if the component already shows the oldest snapshot.
+    #[allow(clippy::must_use_candidate)]
+    pub fn step_back(&self) -> bool {
+        let position = self.position();
+        position > 0 && self.go_to(position - 1)
+    }
+
+    /// Restores the next snapshot.
+    ///
+    /// Returns [`false`] if the component already shows the latest snapshot.
+    #[allow(clippy::must_use_candidate)]
+    pub fn step_forward(&self) -> bool {
+        self.go_to(self.position() + 1)
+    }
+
+    /// Restores the snapshot at the given position and updates the view.
+    ///
+    /// Returns [`false`] if there's no such snapshot or if the component
+    /// isn't running anymore.
+    pub fn go_to(&self, position: usize) -> bool {
+        let mut inner = self.inner.borrow_mut();
+        let Some(model) = inner.history.get(position).map(self.clone_model) else {
+            return false;
+        };
+        let (Some(state), Some(notifier)) = (inner.state.upgrade(), &inner.notifier) else {
+            return false;
+        };
+
+        let Ok(mut parts) = state.try_borrow_mut() else {
+            tracing::error!("Couldn't restore snapshot while the component is updated");
+            return false;
+        };
+        parts.model = model;
+        drop(parts);
+
+        // Let the runtime update the view.
+        notifier.send(()).ok();
+        inner.position = position;
+        true
+    }
+
+    /// Removes all snapshots except for the current one.
+    pub fn clear(&self) {
+        let mut inner = self.inner.borrow_mut();
+        let position = inner.position;
+        inner.history.truncate(position + 1);
+        inner.history.drain(..position);
+        inner.position = 0;
+    }
+
+    pub(crate) fn attach(&self, state: &Rc<RefCell<ComponentParts<C>>>, notifier: Sender<()>) {
+        let mut inner = self.inner.borrow_mut();
+        inner.state = Rc::downgrade(state);
+        inner.notifier = Some(notifier);
+    }
+
+    pub(crate) fn record(&self, model: &C) {
+        let mut inner = self.inner.borrow_mut();
+
+        // Continue the history from the current position.
+        if !inner.history.is_empty() {
+            let position = inner.position;
+            inner.history.truncate(position + 1);
+        }
+        inner.history.push((self.clone_model)(model));
+
+        if let Some(limit) = inner.limit {
+            let excess = inner.history.len().saturating_sub(limit);
+            inner.history.drain(..excess);
+        }
+        inner.position = inner.history.len() - 1;
+    }
+}
+
+impl<C: Component> Clone for TimeTravelDebugger<C> {
+    fn clone(&self) -> Self {
+        Self {
+            inner: Rc::clone(&self.inner),
+            clone_model: self.clone_model,
+        }
+    }
+}
+
+impl<C: Component> Debug for TimeTravelDebugger<C> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let inner = self.inner.borrow();
+        f.debug_struct("TimeTravelDebugger")
+            .field("snapshots", &inner.history.len())
+            .field("position", &inner.position)
+            .field("limit", &inner.limit)
+            .finish()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::TimeTravelDebugger;
+    use crate::testing::ComponentTester;
+    use crate::{Component, ComponentParts, ComponentSender, SimpleComponent};
+
+    #[derive(Clone)]
+    struct Counter(u8);
+
+    impl SimpleComponent for Counter {
+        type Input = ();
+        type Output = ();
+        type Init = ();
+        type Root = ();
+        type Widgets = ();
+
+        fn init_root() -> Self::Root {}
+
+        fn init(
+            _init: Self::Init,
+            _root: Self::Root,
+            _sender: ComponentSender<Self>,
+        ) -> ComponentParts<Self> {
+            ComponentParts {
+                model: Counter(0),
+                widgets: (),
+            }
+        }
+
+        fn update(&mut self, _message: Self::Input, _sender: ComponentSender<Self>) {
+            self.0 += 1;
+        }
+    }
+
+    #[gtk::test]
+    fn step_through_history() {
+        let debugger = TimeTravelDebugger::new();
+        let builder = Counter::builder().time_travel(&debugger);
+        let mut tester = ComponentTester::from_builder(builder, ());
+
+        tester.send(());
+        tester.send(());
+        assert_eq!(debugger.len(), 3);
+        assert_eq!(debugger.position(), 2);
+
+        assert!(debugger.step_back());
+        assert!(debugger.step_back());
+        assert!(!debugger.step_back());
+        tester.process();
+        assert_eq!(tester.model().0, 0);
+
+        assert!(debugger.step_forward());
+        assert_eq!(tester.model().0, 1);
+        assert_eq!(debugger.snapshot(2).unwrap().0, 2);
+
+        // A new message discards the snapshots after the current one.
+        tester.send(());
+        assert_eq!(debugger.len(), 3);
+        assert_eq!(tester.model().0, 2);
+    }
+}
@@ -5,11 +5,22 @@
 use gtk::glib;
 use tracing::info_span;
 
+use flume::r#async::RecvStream;
+use futures::{FutureExt, Stream, StreamExt};
+
+use super::{panic_message, InputPriority, InputQueue, InputTiming};
+use crate::executor::CommandExecutor;
 use crate::{
     Component, ComponentBuilder, ComponentParts, ComponentSender, GuardedReceiver, Receiver,
     RuntimeSenders, Sender, ShutdownOnDrop, SimpleComponent,
 };
-use std::fmt::Debug;
+use std::fmt::{self, Debug};
+use std::future::Future;
+use std::panic::{self, AssertUnwindSafe};
+use std::pin::Pin;
+use std::sync::Arc;
+use std::task::{Context, Poll};
+use std::time::Duration;
 use std::{any, thread};
 
 /// Receives inputs and outputs in the background.
@@ -74,52 +85,280 @@ where
     /// Starts a worker on a separate thread,
     /// passing ownership to a future attached to a [gtk::glib::MainContext].
     pub fn detach_worker(self, payload: C::Init) -> WorkerHandle<C> {
-        let Self { root, .. } = self;
-
         // Used for all events to be processed by this component's internal service.
-        let (input_sender, input_receiver) = crate::channel::<C::Input>();
+        let (input_sender, input_receiver) = self.input_channel();
+        let (output_sender, output_receiver) = crate::channel();
 
-        let RuntimeSenders {
+        let (shutdown_on_drop, stopped) = WorkerOptions::new(self).spawn(
+            Some(payload),
+            input_sender.clone(),
+            input_receiver,
             output_sender,
-            output_receiver,
+            None,
+        );
+
+        // Give back a type for controlling the component service.
+        WorkerHandle {
+            receiver: output_receiver,
+            runtime: WorkerController {
+                sender: input_sender,
+                shutdown_on_drop,
+                stopped,
+            },
+        }
+    }
+
+    /// Starts a worker on a separate thread that is restarted if it panics.
+    ///
+    /// Panics of the worker are caught and reported by the [`Supervisor`],
+    /// which decides whether the worker is initialized again.
+    /// The worker is initialized on its thread, so panics during the first
+    /// initialization are handled in the same way.
+    /// Inputs that are sent while the worker restarts are queued.
+    #[must_use]
+    pub fn detach_supervised_worker(self, supervisor: Supervisor<C>) -> WorkerHandle<C> {
+        let (input_sender, input_receiver) = self.input_channel();
+        let (output_sender, output_receiver) = crate::channel();
+
+        let (shutdown_on_drop, stopped) = WorkerOptions::new(self).spawn(
+            None,
+            input_sender.clone(),
+            input_receiver,
+            output_sender,
+            Some(supervisor),
+        );
+
+        WorkerHandle {
+            receiver: output_receiver,
+            runtime: WorkerController {
+                sender: input_sender,
+                shutdown_on_drop,
+                stopped,
+            },
+        }
+    }
+
+    /// Starts `size` workers on separate threads that share one input queue.
+    ///
+    /// Every input is processed by the next worker that is idle, so CPU-bound
+    /// jobs can use several cores.
+    /// The outputs of all workers are received together.
+    /// `init` creates the initial parameters of each worker from its index.
+    ///
+    /// The [`input_timing()`](Self::input_timing) and [`input_priority()`](Self::input_priority)
+    /// apply to the shared queue, so each input waits there until it's due
+    /// and an idle worker takes it.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `size` is zero.
+    pub fn detach_worker_pool(
+        self,
+        size: usize,
+        mut init: impl FnMut(usize) -> C::Init,
+    ) -> WorkerPoolHandle<C> {
+        assert!(size > 0, "A worker pool needs at least one worker");
+
+        let (input_sender, input_receiver) = self.input_channel();
+        let (output_sender, output_receiver) = crate::channel();
+
+        let mut options = WorkerOptions::new(self);
+        let timing = options.input_timing.take();
+        let priority = options.input_priority.take();
+
+        // Without timing and priority, the workers share the input channel directly.
+        let dispatcher = (timing.is_some() || priority.is_some()).then(|| flume::bounded(0));
+        let worker_receiver = match &dispatcher {
+            Some((_, receiver)) => Receiver(receiver.clone()),
+            None => Receiver(input_receiver.0.clone()),
+        };
+
+        let (workers, stopped): (Vec<_>, Vec<_>) = (0..size)
+            .map(|index| {
+                options.spawn(
+                    Some(init(index)),
+                    input_sender.clone(),
+                    Receiver(worker_receiver.0.clone()),
+                    output_sender.clone(),
+                    None,
+                )
+            })
+            .unzip();
+
+        if let Some((dispatch_sender, _)) = dispatcher {
+            let stopped = stopped
+                .iter()
+                .map(|stopped| Receiver(stopped.0.clone()))
+                .collect();
+            dispatch(input_receiver, dispatch_sender, stopped, timing, priority);
+        }
+
+        WorkerHandle {
+            receiver: output_receiver,
+            runtime: WorkerPool {
+                sender: input_sender,
+                workers,
+                stopped,
+            },
+        }
+    }
+}
+
+/// Applies the input timing and priority of a worker pool on a separate thread.
+///
+/// Inputs are handed to the workers through a channel without capacity,
+/// so they stay in the queue until a worker is idle.
+/// Stops once all workers have stopped.
+fn dispatch<T: Send + 'static>(
+    input_receiver: Receiver<T>,
+    workers: flume::Sender<T>,
+    stopped: Vec<Receiver<()>>,
+    timing: Option<fn(&T) -> InputTiming>,
+    priority: Option<fn(&T) -> InputPriority>,
+) {
+    thread::spawn(move || {
+        let context = glib::MainContext::thread_default().unwrap_or_default();
+        context.block_on(async move {
+            let mut input = InputQueue::new(GuardedReceiver::new(input_receiver), timing, priority);
+            let stopped = futures::future::join_all(stopped.iter().map(Receiver::recv)).fuse();
+            futures::pin_mut!(stopped);
+
+            loop {
+                let message = futures::select!(
+                    message = input => message,
+                    _ = stopped => return,
+                );
+                if workers.send_async(message).await.is_err() {
+                    return;
+                }
+            }
+        });
+    });
+}
+
+/// The settings of a [`ComponentBuilder`] that apply to the runtime of a worker.
+struct WorkerOptions<C: Component> {
+    executor: Option<Arc<dyn CommandExecutor>>,
+    input_timing: Option<fn(&C::Input) -> InputTiming>,
+    input_priority: Option<fn(&C::Input) -> InputPriority>,
+}
+
+impl<C> WorkerOptions<C>
+where
+    C: Component<Root = (), Widgets = ()> + Send,
+    C::Input: Send,
+    C::Output: Send,
+    C::CommandOutput: Send,
+{
+    fn new(builder: ComponentBuilder<C>) -> Self {
+        let ComponentBuilder {
+            executor,
+            input_timing,
+            input_priority,
+            ..
+        } = builder;
+
+        Self {
+            executor,
+            input_timing,
+            input_priority,
+        }
+    }
+
+    /// Initializes a worker and runs it on a new thread.
+    ///
+    /// Without a `payload`, the supervisor initializes the worker on the new thread,
+    /// so panics during the initialization are caught like panics during updates.
+    /// The returned receiver is disconnected once the thread has stopped.
+    fn spawn(
+        &self,
+        payload: Option<C::Init>,
+        input_sender: Sender<C::Input>,
+        input_receiver: Receiver<C::Input>,
+        output_sender: Sender<C::Output>,
+        mut supervisor: Option<Supervisor<C>>,
+    ) -> (ShutdownOnDrop, Receiver<()>) {
+        let input_timing = self.input_timing;
+        let input_priority = self.input_priority;
+
+        let RuntimeSenders {
             cmd_sender,
             cmd_receiver,
             shutdown_notifier,
             shutdown_recipient,
             shutdown_on_drop,
             mut shutdown_event,
+            ..
         } = RuntimeSenders::<C::Output, C::CommandOutput>::new();
 
         // Encapsulates the senders used by component methods.
         let component_sender = ComponentSender::new(
-            input_sender.clone(),
+            input_sender,
             output_sender.clone(),
             cmd_sender,
             shutdown_recipient,
+            self.executor
+                .clone()
+                .unwrap_or_else(crate::executor::thread_executor),
         );
 
-        let mut state = C::init(payload, root, component_sender.clone());
+        let mut state = payload.map(|payload| C::init(payload, (), component_sender.clone()));
+        let (stopped_sender, stopped) = crate::channel::<()>();
 
         thread::spawn(move || {
+            // Dropped after the runtime and the model.
+            let _stopped_sender = stopped_sender;
             let context = glib::MainContext::thread_default().unwrap_or_default();
 
             // Spawns the component's service. It will receive both `Self::Input` and
             // `Self::CommandOutput` messages. It will spawn commands as requested by
             // updates, and send `Self::Output` messages externally.
             context.block_on(async move {
-                let mut cmd = GuardedReceiver::new(cmd_receiver);
-                let mut input = GuardedReceiver::new(input_receiver);
+                let mut cmd = cmd_receiver;
+                let mut input = InputQueue::new(
+                    GuardedReceiver::new(input_receiver),
+                    input_timing,
+                    input_priority,
+                );
 
+                // The reason of the last panic of a supervised worker.
+                let mut failure = None;
                 loop {
-                    futures::select!(
+                    if let Some(message) = failure.take() {
+                        let supervisor = supervisor.as_mut().expect("Only supervised workers fail");
+                        let Some(delay) = supervisor.failed(message, &output_sender) else {
+                            return;
+                        };
+                        futures::select!(
+                            () = glib::timeout_future(delay).fuse() => {}
+                            _ = shutdown_event => {
+                                shutdown_notifier.shutdown();
+                                return;
+                            }
+                        );
+                    }
+
+                    let ComponentParts { model, widgets } = match &mut state {
+                        Some(parts) => parts,
+                        None => {
+                            let supervisor =
+                                supervisor.as_mut().expect("Only supervised workers fail");
+                            let sender = component_sender.clone();
+                            let init = || C::init((supervisor.init)(), (), sender);
+                            match panic::catch_unwind(AssertUnwindSafe(init)) {
+                                Ok(parts) => state.insert(parts),
+                                Err(payload) => {
+                                    failure = Some(panic_message(payload));
+                                    continue;
+                                }
+                            }
+                        }
+                    };
+
+                    let result = futures::select!(
                         // Performs the model update, checking if the update requested a command.
                         // Runs that command asynchronously in the background using tokio.
                         message = input => {
-                            let ComponentParts {
-                                model,
-                                widgets,
-                            } = &mut state;
-
                             let span = info_span!(
                                 "update_with_view",
                                 input=?message,
@@ -128,16 +367,13 @@ where
                             );
                             let _enter = span.enter();
 
-                            model.update_with_view(widgets, message, component_sender.clone(), &root);
+                            Supervisor::run(&mut supervisor, || {
+                                model.update_with_view(widgets, message, component_sender.clone(), &());
+                            })
                         }
 
                         // Handles responses from a command.
                         message = cmd => {
-                            let ComponentParts {
-                                model,
-                                widgets,
-                            } = &mut state;
-
                             let span = info_span!(
                                 "update_cmd_with_view",
                                 cmd_output=?message,
@@ -146,16 +382,13 @@ where
                             );
                             let _enter = span.enter();
 
-                            model.update_cmd_with_view(widgets, message, component_sender.clone(), &root);
+                            Supervisor::run(&mut supervisor, || {
+                                model.update_cmd_with_view(widgets, message, component_sender.clone(), &());
+                            })
                         },
 
                         // Triggered when the component is destroyed
                         _ = shutdown_event => {
-                            let ComponentParts {
-                                model,
-                                widgets,
-                            } = &mut state;
-
                             model.shutdown(widgets, output_sender);
 
                             shutdown_notifier.shutdown();
@@ -163,31 +396,264 @@ where
                             return;
                         }
                     );
+
+                    // Only supervised workers catch panics.
+                    // Their model is dropped and initialized again.
+                    if let (Err(message), Some(_)) = (result, &supervisor) {
+                        state = None;
+                        failure = Some(message);
+                    }
                 }
             });
         });
 
-        // Give back a type for controlling the component service.
-        WorkerHandle {
-            sender: input_sender,
-            receiver: output_receiver,
-            shutdown_on_drop,
+        (shutdown_on_drop, stopped)
+    }
+}
+
+/// Decides when a worker that panicked is restarted.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RestartStrategy {
+    /// Don't restart the worker.
+    Never,
+    /// Restart the worker immediately.
+    Always,
+    /// Restart the worker after a delay that doubles with every
+    /// consecutive panic, starting at `initial` and limited to `max`.
+    Backoff {
+        /// The delay before the first restart.
+        initial: Duration,
+        /// The longest delay between restarts.
+        max: Duration,
+    },
+}
+
+impl RestartStrategy {
+    /// Returns the delay before the next restart or [`None`] if the worker isn't restarted.
+    fn delay(self, failures: u32) -> Option<Duration> {
+        match self {
+            Self::Never => None,
+            Self::Always => Some(Duration::ZERO),
+            Self::Backoff { initial, max } => {
+                let factor = 1_u32 << failures.saturating_sub(1).min(31);
+                Some(initial.saturating_mul(factor).min(max))
+            }
         }
     }
 }
 
+/// Describes a panic of a supervised worker.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct WorkerFailure {
+    /// The panic message.
+    pub message: String,
+    /// The number of consecutive panics, including this one.
+    pub failures: u32,
+    /// Whether the worker is restarted.
+    pub restarting: bool,
+}
+
+type ReportFn<Output> = Box<dyn Fn(&WorkerFailure) -> Option<Output> + Send>;
+
+/// Watches a worker and restarts it after a panic.
+///
+/// Used by [`ComponentBuilder::detach_supervised_worker()`].
+///
+/// ```
+/// # use std::time::Duration;
+/// # use relm4::{ComponentSender, Worker};
+/// # use relm4::component::worker::{RestartStrategy, Supervisor};
+/// # struct Indexer;
+/// # #[derive(Debug)]
+/// # enum IndexerOutput { Crashed }
+/// # impl Worker for Indexer {
+/// #     type Init = ();
+/// #     type Input = ();
+/// #     type Output = IndexerOutput;
+/// #     fn init(_: (), _: ComponentSender<Self>) -> Self { Self }
+/// #     fn update(&mut self, _: (), _: ComponentSender<Self>) {}
+/// # }
+/// let supervisor = Supervisor::<Indexer>::new(|| ())
+///     .restart(RestartStrategy::Backoff {
+///         initial: Duration::from_millis(100),
+///         max: Duration::from_secs(10),
+///     })
+///     .report(|_failure| Some(IndexerOutput::Crashed));
+/// ```
+pub struct Supervisor<C: Component> {
+    strategy: RestartStrategy,
+    init: Box<dyn FnMut() -> C::Init + Send>,
+    report: Option<ReportFn<C::Output>>,
+    // Consecutive panics since the last successful update.
+    failures: u32,
+}
+
+impl<C: Component> Supervisor<C> {
+    /// Creates a supervisor that restarts the worker immediately.
+    ///
+    /// `init` creates the initial parameters every time the worker is started.
+    #[must_use]
+    pub fn new(init: impl FnMut() -> C::Init + Send + 'static) -> Self {
+        Self {
+            strategy: RestartStrategy::Always,
+            init: Box::new(init),
+            report: None,
+            failures: 0,
+        }
+    }
+
+    /// Sets the [`RestartStrategy`].
+    #[must_use]
+    pub fn restart(mut self, strategy: RestartStrategy) -> Self {
+        self.strategy = strategy;
+        self
+    }
+
+    /// Reports panics of the worker as an output.
+    ///
+    /// If `report` returns [`None`], no output is sent.
+    #[must_use]
+    pub fn report(
+        mut self,
+        report: impl Fn(&WorkerFailure) -> Option<C::Output> + Send + 'static,
+    ) -> Self {
+        self.report = Some(Box::new(report));
+        self
+    }
+
+    /// Runs an update and catches panics if the worker is supervised.
+    fn run(supervisor: &mut Option<Self>, update: impl FnOnce()) -> Result<(), String> {
+        let Some(supervisor) = supervisor else {
+            update();
+            return Ok(());
+        };
+
+        panic::catch_unwind(AssertUnwindSafe(update)).map_err(panic_message)?;
+        supervisor.failures = 0;
+        Ok(())
+    }
+
+    /// Reports a panic and returns the delay before the restart.
+    fn failed(&mut self, message: String, output: &Sender<C::Output>) -> Option<Duration> {
+        self.failures = self.failures.saturating_add(1);
+        let delay = self.strategy.delay(self.failures);
+        let failure = WorkerFailure {
+            message,
+            failures: self.failures,
+            restarting: delay.is_some(),
+        };
+        tracing::error!(
+            "Worker {} panicked: {}",
+            any::type_name::<C>(),
+            failure.message
+        );
+
+        if let Some(output_message) = self.report.as_ref().and_then(|report| report(&failure)) {
+            output.send(output_message).ok();
+        }
+        delay
+    }
+}
+
+impl<C: Component> Debug for Supervisor<C> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Supervisor")
+            .field("strategy", &self.strategy)
+            .field("failures", &self.failures)
+            .finish_non_exhaustive()
+    }
+}
+
+/// Where the outputs of a worker are handled.
+///
+/// Used by [`WorkerHandle::connect_receiver_in()`] and [`WorkerHandle::forward_in()`]
+/// to receive outputs on other threads than the main thread of GTK,
+/// for example to pass them to another worker directly.
+#[derive(Debug, Clone, Default)]
+pub enum OutputContext {
+    /// The default [`glib::MainContext`] that runs on the main thread of GTK.
+    #[default]
+    Main,
+    /// A [`glib::MainContext`] of any thread.
+    Context(glib::MainContext),
+    /// A task on the shared runtime of Relm4.
+    Runtime,
+}
+
+impl OutputContext {
+    fn spawn<F: Future<Output = ()> + Send + 'static>(&self, future: F) {
+        match self {
+            Self::Main => {
+                glib::MainContext::default().spawn(future);
+            }
+            Self::Context(context) => {
+                context.spawn(future);
+            }
+            Self::Runtime => {
+                crate::spawn(future);
+            }
+        }
+    }
+}
+
+mod sealed {
+    pub trait Sealed {}
+}
+
+/// The runtime of a single worker or of a pool of workers.
+///
+/// Implemented by [`WorkerController`] and [`WorkerPool`], so the outputs
+/// of both are handled in the same way by [`WorkerHandle`] and [`WorkerStream`].
+pub trait WorkerRuntime<W: Component>: sealed::Sealed + Unpin {
+    /// Provides access to the sender of the inputs.
+    fn sender(&self) -> &Sender<W::Input>;
+
+    /// Keeps the runtime alive after this type is dropped.
+    fn detach_runtime(&mut self);
+}
+
+impl<W: Component> sealed::Sealed for WorkerController<W> {}
+
+impl<W: Component> WorkerRuntime<W> for WorkerController<W> {
+    fn sender(&self) -> &Sender<W::Input> {
+        &self.sender
+    }
+
+    fn detach_runtime(&mut self) {
+        WorkerController::detach_runtime(self);
+    }
+}
+
+impl<W: Component> sealed::Sealed for WorkerPool<W> {}
+
+impl<W: Component> WorkerRuntime<W> for WorkerPool<W> {
+    fn sender(&self) -> &Sender<W::Input> {
+        &self.sender
+    }
+
+    fn detach_runtime(&mut self) {
+        WorkerPool::detach_runtime(self);
+    }
+}
+
 #[derive(Debug)]
 /// Handle to a worker task in the background
-pub struct WorkerHandle<W: Component> {
-    // Sends inputs to the worker.
-    sender: Sender<W::Input>,
-    // Where the worker will send its outputs to.
+///
+/// For a pool of workers, the runtime is a [`WorkerPool`] instead of a
+/// [`WorkerController`], see [`WorkerPoolHandle`].
+pub struct WorkerHandle<W: Component, R: WorkerRuntime<W> = WorkerController<W>> {
+    // Where the workers will send their outputs to.
     receiver: Receiver<W::Output>,
-    // Shutdown the worker when this is dropped
-    shutdown_on_drop: ShutdownOnDrop,
+    // Sends inputs to the workers and shuts them down when dropped.
+    runtime: R,
 }
 
-impl<W: Component> WorkerHandle<W>
+/// Handle to a pool of workers in the background.
+///
+/// Created by [`ComponentBuilder::detach_worker_pool()`].
+pub type WorkerPoolHandle<W> = WorkerHandle<W, WorkerPool<W>>;
+
+impl<W: Component, R: WorkerRuntime<W>> WorkerHandle<W, R>
 where
     W::Input: 'static,
     W::Output: 'static,
@@ -196,24 +662,17 @@ where
     pub fn connect_receiver<F: FnMut(&mut Sender<W::Input>, W::Output) + 'static>(
         self,
         mut func: F,
-    ) -> WorkerController<W> {
-        let Self {
-            sender,
-            receiver,
-            shutdown_on_drop,
-        } = self;
+    ) -> R {
+        let Self { receiver, runtime } = self;
 
-        let mut sender_ = sender.clone();
+        let mut sender = runtime.sender().clone();
         crate::spawn_local(async move {
             while let Some(event) = receiver.recv().await {
-                func(&mut sender_, event);
+                func(&mut sender, event);
             }
         });
 
-        WorkerController {
-            sender,
-            shutdown_on_drop,
-        }
+        runtime
     }
 
     /// Forwards output events to the designated sender.
@@ -221,34 +680,94 @@ where
         self,
         sender: &Sender<X>,
         transform: F,
-    ) -> WorkerController<W> {
-        let Self {
-            sender: own_sender,
-            receiver,
-            shutdown_on_drop,
-        } = self;
+    ) -> R {
+        let Self { receiver, runtime } = self;
 
         crate::spawn_local(receiver.forward(sender.clone(), transform));
-        WorkerController {
-            sender: own_sender,
-            shutdown_on_drop,
-        }
+        runtime
+    }
+
+    /// Like [`connect_receiver()`](Self::connect_receiver), but handles the outputs
+    /// in the given [`OutputContext`].
+    pub fn connect_receiver_in<F>(self, context: &OutputContext, mut func: F) -> R
+    where
+        F: FnMut(&mut Sender<W::Input>, W::Output) + Send + 'static,
+        W::Input: Send,
+        W::Output: Send,
+    {
+        let Self { receiver, runtime } = self;
+
+        let mut sender = runtime.sender().clone();
+        context.spawn(async move {
+            while let Some(event) = receiver.recv().await {
+                func(&mut sender, event);
+            }
+        });
+
+        runtime
+    }
+
+    /// Like [`forward()`](Self::forward), but forwards the outputs
+    /// in the given [`OutputContext`].
+    ///
+    /// ```
+    /// # use relm4::{ComponentSender, Worker};
+    /// # use relm4::component::worker::OutputContext;
+    /// # struct Parser;
+    /// # impl Worker for Parser {
+    /// #     type Init = ();
+    /// #     type Input = String;
+    /// #     type Output = Vec<String>;
+    /// #     fn init(_: (), _: ComponentSender<Self>) -> Self { Self }
+    /// #     fn update(&mut self, _: String, _: ComponentSender<Self>) {}
+    /// # }
+    /// # struct Indexer;
+    /// # impl Worker for Indexer {
+    /// #     type Init = ();
+    /// #     type Input = Vec<String>;
+    /// #     type Output = ();
+    /// #     fn init(_: (), _: ComponentSender<Self>) -> Self { Self }
+    /// #     fn update(&mut self, _: Vec<String>, _: ComponentSender<Self>) {}
+    /// # }
+    /// let indexer = Indexer::builder().detach_worker(()).detach();
+    /// // The parser feeds the indexer without going through the main thread.
+    /// let parser = Parser::builder().detach_worker(()).forward_in(
+    ///     &OutputContext::Runtime,
+    ///     indexer.sender(),
+    ///     |words| words,
+    /// );
+    /// ```
+    pub fn forward_in<X, F>(self, context: &OutputContext, sender: &Sender<X>, transform: F) -> R
+    where
+        X: Send + 'static,
+        F: (Fn(W::Output) -> X) + Send + 'static,
+        W::Output: Send,
+    {
+        let Self { receiver, runtime } = self;
+
+        context.spawn(receiver.forward(sender.clone(), transform));
+        runtime
     }
 
-    /// Ignore outputs from the component and finish the builder.
+    /// Convert this type into a [`Stream`](futures::Stream) that yields the outputs
+    /// of the workers.
+    ///
+    /// The stream implements [`Send`], so it can be used in async components and commands.
     #[must_use]
-    pub fn detach(self) -> WorkerController<W> {
-        let Self {
-            sender,
-            shutdown_on_drop,
-            ..
-        } = self;
+    pub fn into_stream(self) -> WorkerStream<W, R> {
+        let Self { receiver, runtime } = self;
 
-        WorkerController {
-            sender,
-            shutdown_on_drop,
+        WorkerStream {
+            stream: receiver.into_stream(),
+            runtime,
         }
     }
+
+    /// Ignore outputs from the workers and finish the builder.
+    #[must_use]
+    pub fn detach(self) -> R {
+        self.runtime
+    }
 }
 
 /// Sends inputs to a worker. On drop, shuts down the worker.
@@ -258,12 +777,17 @@ pub struct WorkerController<W: Component> {
     sender: Sender<W::Input>,
     // Shutdown the worker when this is dropped
     shutdown_on_drop: ShutdownOnDrop,
+    // Disconnected once the worker has stopped.
+    stopped: Receiver<()>,
 }
 
 impl<W: Component> WorkerController<W> {
     /// Emits an input to the component.
+    ///
+    /// **This method ignores errors.**
+    /// Only a log message will appear if the worker stopped, for example after a panic.
     pub fn emit(&self, event: W::Input) {
-        self.sender.send(event).unwrap();
+        self.sender.emit(event);
     }
 
     /// Provides access to the component's sender.
@@ -279,4 +803,452 @@ impl<W: Component> WorkerController<W> {
     pub fn detach_runtime(&mut self) {
         self.shutdown_on_drop.deactivate();
     }
+
+    /// Shuts down the worker and waits until [`Component::shutdown()`]
+    /// has run and the worker is dropped.
+    ///
+    /// If the runtime was detached with [`detach_runtime()`](Self::detach_runtime),
+    /// this waits until the app is closed.
+    pub async fn shutdown(self) {
+        let Self {
+            shutdown_on_drop,
+            stopped,
+            ..
+        } = self;
+
+        drop(shutdown_on_drop);
+        stopped.recv().await;
+    }
+}
+
+/// Yields [`Component::Output`] values of a worker as a stream.
+///
+/// Created by [`WorkerHandle::into_stream()`].
+/// Like the [`WorkerController`], it sends inputs to the worker and shuts
+/// the worker down when it's dropped.
+/// Also, this type implements [`Send`] so using it in commands is
+/// possible.
+pub struct WorkerStream<W: Component, R: WorkerRuntime<W> = WorkerController<W>> {
+    // The outputs of the workers.
+    stream: RecvStream<'static, W::Output>,
+    runtime: R,
+}
+
+/// Yields [`Component::Output`] values of all workers of a pool as a stream.
+///
+/// Created by [`WorkerPoolHandle::into_stream()`].
+/// Like the [`WorkerPool`], it sends inputs to the workers and shuts
+/// them down when it's dropped.
+pub type WorkerPoolStream<W> = WorkerStream<W, WorkerPool<W>>;
+
+impl<W: Component, R: WorkerRuntime<W>> Stream for WorkerStream<W, R> {
+    type Item = W::Output;
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        self.stream.poll_next_unpin(cx)
+    }
+}
+
+impl<W: Component, R: WorkerRuntime<W>> WorkerStream<W, R> {
+    /// Emits an input to the worker or, for pools, to the next idle worker.
+    ///
+    /// **This method ignores errors.**
+    /// Only a log message will appear if the workers stopped.
+    pub fn emit(&self, event: W::Input) {
+        self.runtime.sender().emit(event);
+    }
+
+    /// Provides access to the sender of the inputs.
+    #[must_use]
+    pub fn sender(&self) -> &Sender<W::Input> {
+        self.runtime.sender()
+    }
+
+    /// Dropping this type will usually stop the runtime of the workers.
+    /// With this method you can give the runtime a static lifetime.
+    /// In other words, dropping the stream will not stop
+    /// the runtime anymore, it will run until the app is closed.
+    pub fn detach_runtime(&mut self) {
+        self.runtime.detach_runtime();
+    }
+}
+
+impl<W: Component> WorkerStream<W> {
+    /// Stops yielding outputs and returns the [`WorkerController`],
+    /// for example to [`shutdown()`](WorkerController::shutdown) the worker.
+    #[must_use]
+    pub fn into_controller(self) -> WorkerController<W> {
+        self.runtime
+    }
+}
+
+impl<W: Component> WorkerPoolStream<W> {
+    /// Stops yielding outputs and returns the [`WorkerPool`],
+    /// for example to [`shutdown()`](WorkerPool::shutdown) the workers.
+    #[must_use]
+    pub fn into_pool(self) -> WorkerPool<W> {
+        self.runtime
+    }
+}
+
+impl<W: Component, R: WorkerRuntime<W> + Debug> Debug for WorkerStream<W, R> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("WorkerStream")
+            .field("stream", &"<RecvStream>")
+            .field("runtime", &self.runtime)
+            .finish()
+    }
+}
+
+/// Sends inputs to a pool of workers. On drop, shuts down all workers.
+pub struct WorkerPool<W: Component> {
+    // Sends inputs to the shared queue of the workers.
+    sender: Sender<W::Input>,
+    // Shutdown the workers when this is dropped
+    workers: Vec<ShutdownOnDrop>,
+    // Disconnected once the respective worker has stopped.
+    stopped: Vec<Receiver<()>>,
+}
+
+impl<W: Component> WorkerPool<W> {
+    /// Emits an input to the next idle worker.
+    ///
+    /// **This method ignores errors.**
+    /// Only a log message will appear if all workers stopped.
+    pub fn emit(&self, event: W::Input) {
+        self.sender.emit(event);
+    }
+
+    /// Provides access to the sender of the shared input queue.
+    #[must_use]
+    pub const fn sender(&self) -> &Sender<W::Input> {
+        &self.sender
+    }
+
+    /// Returns the number of workers in the pool.
+    #[must_use]
+    pub fn size(&self) -> usize {
+        self.workers.len()
+    }
+
+    /// Dropping this type will usually stop the runtime of the workers.
+    /// With this method you can give the runtimes a static lifetime.
+    /// In other words, dropping the [`WorkerPool`] will not stop
+    /// the workers anymore, they will run until the app is closed.
+    pub fn detach_runtime(&mut self) {
+        for worker in &mut self.workers {
+            worker.deactivate();
+        }
+    }
+
+    /// Shuts down all workers and waits until [`Component::shutdown()`]
+    /// has run and each worker is dropped.
+    ///
+    /// If the runtimes were detached with [`detach_runtime()`](Self::detach_runtime),
+    /// this waits until the app is closed.
+    pub async fn shutdown(self) {
+        let Self {
+            workers, stopped, ..
+        } = self;
+
+        drop(workers);
+        for stopped in stopped {
+            stopped.recv().await;
+        }
+    }
+}
+
+impl<W: Component> Debug for WorkerPool<W> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("WorkerPool")
+            .field("sender", &self.sender)
+            .field("size", &self.size())
+            .finish()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::sync::atomic::{AtomicBool, Ordering};
+    use std::sync::Arc;
+    use std::thread;
+    use std::time::Duration;
+
+    use futures::StreamExt;
+
+    use super::{OutputContext, RestartStrategy, Supervisor, Worker};
+    use crate::component::InputPriority;
+    use crate::{Component, ComponentSender};
+
+    struct Doubler;
+
+    impl Worker for Doubler {
+        type Init = ();
+        type Input = u8;
+        type Output = u8;
+
+        fn init(_init: Self::Init, _sender: ComponentSender<Self>) -> Self {
+            Doubler
+        }
+
+        fn update(&mut self, message: Self::Input, sender: ComponentSender<Self>) {
+            sender.output(message * 2).unwrap();
+        }
+    }
+
+    #[test]
+    fn worker_pool() {
+        let handle = Doubler::builder().detach_worker_pool(3, |_| ());
+        for value in 0..10 {
+            handle.runtime.emit(value);
+        }
+
+        let mut outputs: Vec<u8> = (0..10)
+            .map(|_| handle.receiver.recv_sync().unwrap())
+            .collect();
+        outputs.sort_unstable();
+        assert_eq!(outputs, (0..10).map(|value| value * 2).collect::<Vec<_>>());
+
+        let pool = handle.detach();
+        assert_eq!(pool.size(), 3);
+    }
+
+    // Reports the index of the worker that processed a job.
+    struct Slow(usize);
+
+    impl Worker for Slow {
+        type Init = usize;
+        type Input = u8;
+        type Output = usize;
+
+        fn init(init: Self::Init, _sender: ComponentSender<Self>) -> Self {
+            Slow(init)
+        }
+
+        fn update(&mut self, _message: Self::Input, sender: ComponentSender<Self>) {
+            thread::sleep(Duration::from_millis(20));
+            sender.output(self.0).unwrap();
+        }
+    }
+
+    #[test]
+    fn pool_priority_spreads_inputs() {
+        let handle = Slow::builder()
+            .input_priority(|job| {
+                if *job == 0 {
+                    InputPriority::High
+                } else {
+                    InputPriority::Normal
+                }
+            })
+            .detach_worker_pool(3, |index| index);
+        for job in 0..6 {
+            handle.runtime.emit(job);
+        }
+
+        let mut workers: Vec<usize> = (0..6)
+            .map(|_| handle.receiver.recv_sync().unwrap())
+            .collect();
+        workers.sort_unstable();
+        workers.dedup();
+        assert!(workers.len() > 1, "Only worker {workers:?} took inputs");
+    }
+
+    // Panics for zero.
+    struct Divider;
+
+    #[derive(Debug, PartialEq, Eq)]
+    enum DividerOutput {
+        Value(u8),
+        Failed { failures: u32, restarting: bool },
+    }
+
+    impl Worker for Divider {
+        type Init = ();
+        type Input = u8;
+        type Output = DividerOutput;
+
+        fn init(_init: Self::Init, _sender: ComponentSender<Self>) -> Self {
+            Divider
+        }
+
+        fn update(&mut self, message: Self::Input, sender: ComponentSender<Self>) {
+            assert_ne!(message, 0, "division by zero");
+            sender.output(DividerOutput::Value(60 / message)).unwrap();
+        }
+    }
+
+    fn report(
+        strategy: RestartStrategy,
+        init: impl FnMut() + Send + 'static,
+    ) -> Supervisor<Divider> {
+        Supervisor::new(init).restart(strategy).report(|failure| {
+            assert!(failure.message.contains("division by zero"));
+            Some(DividerOutput::Failed {
+                failures: failure.failures,
+                restarting: failure.restarting,
+            })
+        })
+    }
+
+    #[test]
+    fn supervised_worker_restarts() {
+        let handle =
+            Divider::builder().detach_supervised_worker(report(RestartStrategy::Always, || ()));
+        for value in [0, 0, 3] {
+            handle.runtime.emit(value);
+        }
+
+        let outputs: Vec<_> = (0..3)
+            .map(|_| handle.receiver.recv_sync().unwrap())
+            .collect();
+        assert_eq!(
+            outputs,
+            [
+                DividerOutput::Failed {
+                    failures: 1,
+                    restarting: true
+                },
+                DividerOutput::Failed {
+                    failures: 2,
+                    restarting: true
+                },
+                DividerOutput::Value(20),
+            ]
+        );
+    }
+
+    #[test]
+    fn supervised_worker_init_restarts() {
+        let mut attempts = 0;
+        let supervisor = report(RestartStrategy::Always, move || {
+            attempts += 1;
+            assert!(attempts > 1, "division by zero");
+        });
+        let handle = Divider::builder().detach_supervised_worker(supervisor);
+        handle.runtime.emit(3);
+
+        assert_eq!(
+            handle.receiver.recv_sync(),
+            Some(DividerOutput::Failed {
+                failures: 1,
+                restarting: true
+            })
+        );
+        assert_eq!(handle.receiver.recv_sync(), Some(DividerOutput::Value(20)));
+    }
+
+    #[test]
+    fn supervised_worker_stops() {
+        let handle =
+            Divider::builder().detach_supervised_worker(report(RestartStrategy::Never, || ()));
+        handle.runtime.emit(0);
+        assert_eq!(
+            handle.receiver.recv_sync(),
+            Some(DividerOutput::Failed {
+                failures: 1,
+                restarting: false
+            })
+        );
+
+        // Emitting to a stopped worker doesn't panic.
+        let controller = handle.detach();
+        controller.emit(1);
+    }
+
+    #[test]
+    fn backoff_delay() {
+        let strategy = RestartStrategy::Backoff {
+            initial: Duration::from_millis(100),
+            max: Duration::from_secs(1),
+        };
+        let delays: Vec<_> = (1..=6).map(|failures| strategy.delay(failures)).collect();
+        assert_eq!(
+            delays,
+            [100, 200, 400, 800, 1000, 1000].map(|millis| Some(Duration::from_millis(millis)))
+        );
+        assert_eq!(RestartStrategy::Never.delay(1), None);
+    }
+
+    #[test]
+    fn worker_stream() {
+        let mut stream = Doubler::builder().detach_worker(()).into_stream();
+        stream.emit(1);
+        stream.emit(2);
+
+        futures::executor::block_on(async {
+            assert_eq!(stream.next().await, Some(2));
+            assert_eq!(stream.next().await, Some(4));
+        });
+    }
+
+    // Sets the flag when it's dropped.
+    struct Flagged(Arc<AtomicBool>);
+
+    impl Drop for Flagged {
+        fn drop(&mut self) {
+            self.0.store(true, Ordering::SeqCst);
+        }
+    }
+
+    impl Worker for Flagged {
+        type Init = Arc<AtomicBool>;
+        type Input = ();
+        type Output = ();
+
+        fn init(init: Self::Init, _sender: ComponentSender<Self>) -> Self {
+            Flagged(init)
+        }
+
+        fn update(&mut self, _message: Self::Input, _sender: ComponentSender<Self>) {}
+    }
+
+    #[test]
+    fn await_shutdown() {
+        let dropped = Arc::new(AtomicBool::new(false));
+        let controller = Flagged::builder().detach_worker(dropped.clone()).detach();
+        controller.emit(());
+
+        futures::executor::block_on(controller.shutdown());
+        assert!(dropped.load(Ordering::SeqCst));
+    }
+
+    #[test]
+    fn pool_stream() {
+        let mut stream = Doubler::builder()
+            .detach_worker_pool(2, |_| ())
+            .into_stream();
+        stream.emit(1);
+        stream.emit(2);
+
+        futures::executor::block_on(async {
+            let mut outputs = [stream.next().await, stream.next().await];
+            outputs.sort_unstable();
+            assert_eq!(outputs, [Some(2), Some(4)]);
+        });
+    }
+
+    #[test]
+    fn await_pool_shutdown() {
+        let flags: Vec<_> = (0..2).map(|_| Arc::new(AtomicBool::new(false))).collect();
+        let pool = Flagged::builder()
+            .detach_worker_pool(2, |index| flags[index].clone())
+            .detach();
+        pool.emit(());
+
+        futures::executor::block_on(pool.shutdown());
+        assert!(flags.iter().all(|flag| flag.load(Ordering::SeqCst)));
+    }
+
+    #[test]
+    fn forward_between_workers() {
+        let last = Doubler::builder().detach_worker(());
+        let first = Doubler::builder().detach_worker(()).forward_in(
+            &OutputContext::Runtime,
+            &last.runtime.sender,
+            |value| value + 1,
+        );
+        first.emit(3);
+        assert_eq!(last.receiver.recv_sync(), Some(14));
+    }
 }
@@ -0,0 +1,72 @@
+//! Executors that run the commands of components.
+//!
+//! By default, all commands run on the shared runtime of Relm4.
+//! A custom [`CommandExecutor`] can be set for a single component with
+//! [`ComponentBuilder::command_executor()`](crate::ComponentBuilder::command_executor)
+//! or for all components launched on the current thread with [`set_thread_executor()`].
+
+use std::cell::RefCell;
+use std::fmt::Debug;
+use std::sync::Arc;
+
+use once_cell::sync::Lazy;
+
+use crate::component::CommandFuture;
+
+/// A synchronous command that is passed to a [`CommandExecutor`].
+pub type BlockingCommand = Box<dyn FnOnce() + Send>;
+
+/// Runs the commands spawned by components and factories.
+///
+/// The `name` passed to each method is the type name of the command,
+/// which is useful to tell commands apart in logs or tests.
+pub trait CommandExecutor: Debug + Send + Sync + 'static {
+    /// Spawns an asynchronous command.
+    fn spawn(&self, name: &'static str, command: CommandFuture);
+
+    /// Spawns a synchronous command that might block its thread.
+    fn spawn_blocking(&self, name: &'static str, command: BlockingCommand);
+}
+
+/// The default [`CommandExecutor`] which runs commands on the shared runtime.
+///
+/// The number of threads used by the runtime can be configured with
+/// [`RELM_THREADS`](crate::RELM_THREADS) and [`RELM_BLOCKING_THREADS`](crate::RELM_BLOCKING_THREADS).
+#[derive(Debug, Default, Clone, Copy)]
+pub struct RuntimeExecutor;
+
+impl CommandExecutor for RuntimeExecutor {
+    fn spawn(&self, _name: &'static str, command: CommandFuture) {
+        crate::spawn(command);
+    }
+
+    fn spawn_blocking(&self, _name: &'static str, command: BlockingCommand) {
+        crate::spawn_blocking(command);
+    }
+}
+
+static RUNTIME_EXECUTOR: Lazy<Arc<dyn CommandExecutor>> = Lazy::new(|| Arc::new(RuntimeExecutor));
+
+thread_local! {
+    static THREAD_EXECUTOR: RefCell<Option<Arc<dyn CommandExecutor>>> = RefCell::default();
+}
+
+/// Sets the [`CommandExecutor`] for all components and factories
+/// that are launched on the current thread from now on.
+///
+/// Components that were already launched keep their executor.
+pub fn set_thread_executor(executor: impl CommandExecutor) {
+    THREAD_EXECUTOR.with(|cell| *cell.borrow_mut() = Some(Arc::new(executor)));
+}
+
+/// Restores the default [`RuntimeExecutor`] for the current thread.
+pub fn reset_thread_executor() {
+    THREAD_EXECUTOR.with(|cell| *cell.borrow_mut() = None);
+}
+
+/// Returns the executor for components launched on the current thread.
+pub(crate) fn thread_executor() -> Arc<dyn CommandExecutor> {
+    THREAD_EXECUTOR
+        .with(|cell| cell.borrow().clone())
+        .unwrap_or_else(|| RUNTIME_EXECUTOR.clone())
+}
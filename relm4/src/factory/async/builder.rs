@@ -7,36 +7,47 @@ use super::{AsyncFactoryComponent, AsyncFactoryHandle};
 
 use crate::channel::AsyncFactorySender;
 use crate::factory::{DataGuard, DynamicIndex, FactoryView};
+use crate::inspector::{ComponentKind, Inspection};
 use crate::runtime_util::GuardedReceiver;
 use crate::shutdown::ShutdownSender;
-use crate::{shutdown, Receiver, Sender};
+use crate::{shutdown, CommandReceiver, Receiver, Sender};
 
 pub(super) struct AsyncFactoryBuilder<C: AsyncFactoryComponent> {
     init: C::Init,
     pub(super) root_widget: C::Root,
     pub(super) component_sender: AsyncFactorySender<C>,
     input_receiver: Receiver<C::Input>,
-    cmd_receiver: Receiver<C::CommandOutput>,
+    cmd_receiver: CommandReceiver<C::CommandOutput>,
     shutdown_notifier: ShutdownSender,
+    debug_model: Option<fn(&C) -> String>,
 }
 
 impl<C: AsyncFactoryComponent> AsyncFactoryBuilder<C>
 where
     <C::ParentWidget as FactoryView>::ReturnedWidget: Clone,
 {
-    pub(super) fn new(init: C::Init, output_sender: Sender<C::Output>) -> Self {
+    pub(super) fn new(
+        init: C::Init,
+        output_sender: Sender<C::Output>,
+        debug_model: Option<fn(&C) -> String>,
+    ) -> Self {
         // Used for all events to be processed by this component's internal service.
         let (input_sender, input_receiver) = crate::channel::<C::Input>();
 
         // Sends messages from commands executed from the background.
-        let (cmd_sender, cmd_receiver) = crate::channel::<C::CommandOutput>();
+        let (cmd_sender, cmd_receiver) = crate::command_channel::<C::CommandOutput>();
 
         // Notifies the component's child commands that it is now deceased.
         let (shutdown_notifier, shutdown_receiver) = shutdown::channel();
 
         // Encapsulates the senders used by component methods.
-        let component_sender =
-            AsyncFactorySender::new(input_sender, output_sender, cmd_sender, shutdown_receiver);
+        let component_sender = AsyncFactorySender::new(
+            input_sender,
+            output_sender,
+            cmd_sender,
+            shutdown_receiver,
+            crate::executor::thread_executor(),
+        );
 
         let root_widget = C::init_root();
 
@@ -47,6 +58,7 @@ where
             input_receiver,
             cmd_receiver,
             shutdown_notifier,
+            debug_model,
         }
     }
 
@@ -63,6 +75,7 @@ where
             cmd_receiver,
             shutdown_notifier,
             init,
+            debug_model,
         } = self;
 
         // Gets notifications when a component's model and view is updated externally.
@@ -85,6 +98,7 @@ where
                 input_receiver,
                 cmd_receiver,
                 notifier_receiver,
+                debug_model,
             };
 
             crate::spawn_local(async move {
@@ -129,8 +143,9 @@ struct FutureData<C: AsyncFactoryComponent> {
     root: C::Root,
     returned_widget: <C::ParentWidget as FactoryView>::ReturnedWidget,
     input_receiver: Receiver<C::Input>,
-    cmd_receiver: Receiver<C::CommandOutput>,
+    cmd_receiver: CommandReceiver<C::CommandOutput>,
     notifier_receiver: Receiver<()>,
+    debug_model: Option<fn(&C) -> String>,
 }
 
 impl<C: AsyncFactoryComponent> FutureData<C> {
@@ -144,6 +159,7 @@ impl<C: AsyncFactoryComponent> FutureData<C> {
             cmd_receiver,
             input_receiver,
             notifier_receiver,
+            debug_model,
         } = self;
 
         let mut data = Box::new(data);
@@ -152,6 +168,16 @@ impl<C: AsyncFactoryComponent> FutureData<C> {
 
         let output_sender = component_sender.output_sender().clone();
 
+        let inspection = Inspection::register(
+            ComponentKind::AsyncFactoryComponent,
+            component_sender.input_sender(),
+            C::id,
+            debug_model,
+        );
+        if let Some(inspection) = &inspection {
+            inspection.update(&data);
+        }
+
         // Spawns the component's service. It will receive both `Self::Input` and
         // `Self::CommandOutput` messages. It will spawn commands as requested by
         // updates, and send `Self::Output` messages externally.
@@ -162,7 +188,7 @@ impl<C: AsyncFactoryComponent> FutureData<C> {
             output_sender,
             |mut model, mut widgets| async move {
                 let mut notifier = GuardedReceiver::new(notifier_receiver);
-                let mut cmd = GuardedReceiver::new(cmd_receiver);
+                let mut cmd = cmd_receiver;
                 let mut input = GuardedReceiver::new(input_receiver);
                 loop {
                     futures::select!(
@@ -178,6 +204,10 @@ impl<C: AsyncFactoryComponent> FutureData<C> {
                             let _enter = span.enter();
 
                             model.update_with_view(&mut widgets, message, component_sender.clone()).await;
+
+                            if let Some(inspection) = &inspection {
+                                inspection.update(&model);
+                            }
                         }
 
                         // Handles responses from a command.
@@ -191,11 +221,19 @@ impl<C: AsyncFactoryComponent> FutureData<C> {
                             let _enter = span.enter();
 
                             model.update_cmd_with_view(&mut widgets, message, component_sender.clone()).await;
+
+                            if let Some(inspection) = &inspection {
+                                inspection.update(&model);
+                            }
                         }
 
                         // Triggered when the model and view have been updated externally.
                         _ = notifier => {
                             model.update_view(&mut widgets, component_sender.clone());
+
+                            if let Some(inspection) = &inspection {
+                                inspection.update(&model);
+                            }
                         }
                     );
                 }
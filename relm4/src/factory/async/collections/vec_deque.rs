@@ -1,3 +1,4 @@
+use crate::inspector;
 use crate::{Receiver, Sender};
 
 use crate::factory::r#async::component_storage::AsyncComponentStorage;
@@ -9,6 +10,7 @@ use super::{ModelStateValue, RenderedState};
 
 use std::collections::hash_map::DefaultHasher;
 use std::collections::VecDeque;
+use std::fmt::Debug;
 use std::hash::Hash;
 use std::iter::FusedIterator;
 use std::marker::PhantomData;
@@ -232,7 +234,7 @@ where
             states.index.increment();
         }
 
-        let builder = AsyncFactoryBuilder::new(init, self.output_sender.clone());
+        let builder = AsyncFactoryBuilder::new(init, self.output_sender.clone(), self.debug_model);
 
         self.inner
             .components
@@ -395,6 +397,7 @@ pub struct AsyncFactoryVecDequeBuilder<C>
 where
     C: AsyncFactoryComponent,
 {
+    debug_model: Option<fn(&C) -> String>,
     _component: PhantomData<C>,
 }
 
@@ -427,6 +430,7 @@ where
     #[must_use]
     pub fn new() -> Self {
         Self {
+            debug_model: None,
             _component: PhantomData,
         }
     }
@@ -440,10 +444,23 @@ where
             widget,
             output_sender,
             output_receiver,
+            debug_model: self.debug_model,
         }
     }
 }
 
+impl<C> AsyncFactoryVecDequeBuilder<C>
+where
+    C: AsyncFactoryComponent + Debug,
+{
+    /// Show a [`Debug`] view of the models of the elements in the [`inspector`](crate::inspector).
+    #[must_use]
+    pub fn inspect(mut self) -> Self {
+        self.debug_model = Some(inspector::debug_model);
+        self
+    }
+}
+
 #[derive(Debug)]
 /// Second stage of the builder-pattern for building a [`AsyncFactoryVecDeque`].
 pub struct AsyncFactoryVecDequeConnector<C>
@@ -453,6 +470,7 @@ where
     widget: C::ParentWidget,
     output_sender: Sender<C::Output>,
     output_receiver: Receiver<C::Output>,
+    debug_model: Option<fn(&C) -> String>,
 }
 
 impl<C> AsyncFactoryVecDequeConnector<C>
@@ -471,6 +489,7 @@ where
             widget,
             output_sender,
             output_receiver,
+            debug_model,
         } = self;
 
         let sender_clone = sender_.clone();
@@ -485,6 +504,7 @@ where
         AsyncFactoryVecDeque {
             widget,
             output_sender,
+            debug_model,
             components: VecDeque::new(),
             model_state: VecDeque::new(),
             rendered_state: VecDeque::new(),
@@ -498,11 +518,13 @@ where
         let Self {
             widget,
             output_sender,
+            debug_model,
             ..
         } = self;
         AsyncFactoryVecDeque {
             widget,
             output_sender,
+            debug_model,
             components: VecDeque::new(),
             model_state: VecDeque::new(),
             rendered_state: VecDeque::new(),
@@ -523,6 +545,7 @@ where
 {
     widget: C::ParentWidget,
     output_sender: Sender<C::Output>,
+    debug_model: Option<fn(&C) -> String>,
     components: VecDeque<AsyncComponentStorage<C>>,
     model_state: VecDeque<ModelStateValue>,
     rendered_state: VecDeque<RenderedState>,
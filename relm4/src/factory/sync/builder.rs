@@ -1,10 +1,19 @@
 use super::{FactoryComponent, FactoryHandle};
 
+use crate::component::panic_message;
 use crate::factory::{DataGuard, FactorySender, FactoryView};
+use crate::inspector::{ComponentKind, Inspection};
 use crate::shutdown::ShutdownSender;
-use crate::{shutdown, GuardedReceiver, Receiver, Sender};
+use crate::{shutdown, CommandReceiver, GuardedReceiver, Receiver, Sender};
 
-use std::any;
+use std::any::{self, Any};
+use std::cell::{Cell, RefCell};
+use std::fmt;
+use std::panic::{self, AssertUnwindSafe};
+use std::rc::Rc;
+
+use futures::FutureExt;
+use gtk::prelude::WidgetExt;
 
 use tracing::info_span;
 
@@ -14,24 +23,71 @@ pub(super) struct FactoryBuilder<C: FactoryComponent> {
     pub(super) root_widget: C::Root,
     pub(super) component_sender: FactorySender<C>,
     pub(super) input_receiver: Receiver<C::Input>,
-    pub(super) cmd_receiver: Receiver<C::CommandOutput>,
+    pub(super) cmd_receiver: CommandReceiver<C::CommandOutput>,
     pub(super) shutdown_notifier: ShutdownSender,
+    pub(super) options: ElementOptions<C>,
+}
+
+/// Runtime options that apply to all elements of a factory.
+pub(super) struct ElementOptions<C: FactoryComponent> {
+    pub(super) batch_inputs: bool,
+    // Only set if view updates are suspended while the root is hidden.
+    pub(super) suspend: Option<fn(&C::Root) -> &gtk::Widget>,
+    pub(super) debug_model: Option<fn(&C) -> String>,
+}
+
+impl<C: FactoryComponent> Default for ElementOptions<C> {
+    fn default() -> Self {
+        Self {
+            batch_inputs: false,
+            suspend: None,
+            debug_model: None,
+        }
+    }
+}
+
+impl<C: FactoryComponent> Clone for ElementOptions<C> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<C: FactoryComponent> Copy for ElementOptions<C> {}
+
+impl<C: FactoryComponent> fmt::Debug for ElementOptions<C> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ElementOptions")
+            .field("batch_inputs", &self.batch_inputs)
+            .field("suspend", &self.suspend.is_some())
+            .field("debug_model", &self.debug_model.is_some())
+            .finish()
+    }
 }
 
 impl<C: FactoryComponent> FactoryBuilder<C> {
-    pub(super) fn new(index: &C::Index, init: C::Init, output_sender: Sender<C::Output>) -> Self {
+    pub(super) fn new(
+        index: &C::Index,
+        init: C::Init,
+        output_sender: Sender<C::Output>,
+        options: ElementOptions<C>,
+    ) -> Self {
         // Used for all events to be processed by this component's internal service.
         let (input_sender, input_receiver) = crate::channel::<C::Input>();
 
         // Sends messages from commands executed from the background.
-        let (cmd_sender, cmd_receiver) = crate::channel::<C::CommandOutput>();
+        let (cmd_sender, cmd_receiver) = crate::command_channel::<C::CommandOutput>();
 
         // Notifies the component's child commands that it is now deceased.
         let (shutdown_notifier, shutdown_receiver) = shutdown::channel();
 
         // Encapsulates the senders used by component methods.
-        let component_sender =
-            FactorySender::new(input_sender, output_sender, cmd_sender, shutdown_receiver);
+        let component_sender = FactorySender::new(
+            input_sender,
+            output_sender,
+            cmd_sender,
+            shutdown_receiver,
+            crate::executor::thread_executor(),
+        );
 
         let data = Box::new(C::init_model(init, index, component_sender.clone()));
         let root_widget = data.init_root();
@@ -43,6 +99,7 @@ impl<C: FactoryComponent> FactoryBuilder<C> {
             input_receiver,
             cmd_receiver,
             shutdown_notifier,
+            options,
         }
     }
 
@@ -50,6 +107,8 @@ impl<C: FactoryComponent> FactoryBuilder<C> {
     pub(super) fn launch(
         self,
         index: &C::Index,
+        parent: &C::ParentWidget,
+        position: <C::ParentWidget as FactoryView>::Position,
         returned_widget: <C::ParentWidget as FactoryView>::ReturnedWidget,
     ) -> FactoryHandle<C> {
         let Self {
@@ -59,7 +118,13 @@ impl<C: FactoryComponent> FactoryBuilder<C> {
             input_receiver,
             cmd_receiver,
             shutdown_notifier,
+            options,
         } = self;
+        let ElementOptions {
+            batch_inputs,
+            suspend,
+            debug_model,
+        } = options;
 
         // Gets notifications when a component's model and view is updated externally.
         let (notifier, notifier_receiver) = crate::channel();
@@ -74,6 +139,43 @@ impl<C: FactoryComponent> FactoryBuilder<C> {
         let input_sender = component_sender.input_sender().clone();
         let output_sender = component_sender.output_sender().clone();
 
+        let returned_widget = Rc::new(RefCell::new(returned_widget));
+        let position = Rc::new(RefCell::new(position));
+        let failed = Rc::new(Cell::new(false));
+        let mut isolation = PanicIsolation::<C> {
+            failed: failed.clone(),
+            parent: parent.clone(),
+            position: position.clone(),
+            returned_widget: returned_widget.clone(),
+            output: output_sender.clone(),
+        };
+        let shutdown_failed = failed.clone();
+
+        // Set if view updates were skipped while the root widget was hidden.
+        let stale = Rc::new(Cell::new(false));
+        if let Some(widget) = suspend {
+            let stale = stale.clone();
+            // Don't keep the runtime alive.
+            let notifier = notifier.0.downgrade();
+            widget(&root_widget).connect_map(move |_| {
+                if let (true, Some(notifier)) = (stale.get(), notifier.upgrade()) {
+                    notifier.send(()).ok();
+                }
+            });
+        }
+        let rt_root = root_widget.clone();
+        let is_suspended = move || suspend.is_some_and(|widget| !widget(&rt_root).is_mapped());
+
+        let inspection = Inspection::register(
+            ComponentKind::FactoryComponent,
+            &input_sender,
+            C::id,
+            debug_model,
+        );
+        if let Some(inspection) = &inspection {
+            inspection.update(&data);
+        }
+
         // Spawns the component's service. It will receive both `Self::Input` and
         // `Self::CommandOutput` messages. It will spawn commands as requested by
         // updates, and send `Self::Output` messages externally.
@@ -85,7 +187,7 @@ impl<C: FactoryComponent> FactoryBuilder<C> {
             |mut model, mut widgets| {
                 async move {
                     let mut notifier = GuardedReceiver::new(notifier_receiver);
-                    let mut cmd = GuardedReceiver::new(cmd_receiver);
+                    let mut cmd = cmd_receiver;
                     let mut input = GuardedReceiver::new(input_receiver);
                     loop {
                         futures::select!(
@@ -100,7 +202,34 @@ impl<C: FactoryComponent> FactoryBuilder<C> {
                                 );
                                 let _enter = span.enter();
 
-                                model.update_with_view(&mut widgets, message, component_sender.clone());
+                                isolation.run(&mut **model, |model| {
+                                    let suspended = is_suspended();
+                                    if batch_inputs || suspended {
+                                        // Inputs that are sent during the batch wait for the next one.
+                                        let mut remaining = if batch_inputs { input.len() } else { 0 };
+                                        let mut next = Some(message);
+                                        while let Some(message) = next.take() {
+                                            model.update(message, component_sender.clone());
+                                            if remaining > 0 {
+                                                remaining -= 1;
+                                                next = (&mut input).now_or_never();
+                                            }
+                                        }
+
+                                        if suspended {
+                                            stale.set(true);
+                                        } else {
+                                            // Update the view once after all queued inputs were processed.
+                                            model.update_view(&mut widgets, component_sender.clone());
+                                        }
+                                    } else {
+                                        model.update_with_view(&mut widgets, message, component_sender.clone());
+                                    }
+                                });
+
+                                if let Some(inspection) = &inspection {
+                                    inspection.update(&model);
+                                }
                             }
 
                             // Handles responses from a command.
@@ -113,18 +242,45 @@ impl<C: FactoryComponent> FactoryBuilder<C> {
                                 );
                                 let _enter = span.enter();
 
-                                model.update_cmd_with_view(&mut widgets, message, component_sender.clone());
+                                isolation.run(&mut **model, |model| {
+                                    if is_suspended() {
+                                        model.update_cmd(message, component_sender.clone());
+                                        stale.set(true);
+                                    } else {
+                                        model.update_cmd_with_view(&mut widgets, message, component_sender.clone());
+                                    }
+                                });
+
+                                if let Some(inspection) = &inspection {
+                                    inspection.update(&model);
+                                }
                             }
 
                             // Triggered when the model and view have been updated externally.
                             _ = notifier => {
-                                model.update_view(&mut widgets, component_sender.clone());
+                                isolation.run(&mut **model, |model| {
+                                    if is_suspended() {
+                                        stale.set(true);
+                                    } else {
+                                        stale.set(false);
+                                        model.update_view(&mut widgets, component_sender.clone());
+                                    }
+                                });
+
+                                if let Some(inspection) = &inspection {
+                                    inspection.update(&model);
+                                }
                             }
                         );
                     }
                 }
             },
-            C::shutdown,
+            move |model, widgets, output| {
+                // The model might be inconsistent after a panic.
+                if !shutdown_failed.get() {
+                    model.shutdown(widgets, output);
+                }
+            },
         );
 
         // Give back a type for controlling the component service.
@@ -132,8 +288,348 @@ impl<C: FactoryComponent> FactoryBuilder<C> {
             data,
             root_widget,
             returned_widget,
+            position,
             input: input_sender,
             notifier,
+            failed,
+        }
+    }
+}
+
+/// Catches panics of a factory component so they don't affect the rest of the factory.
+struct PanicIsolation<C: FactoryComponent> {
+    failed: Rc<Cell<bool>>,
+    parent: C::ParentWidget,
+    // Kept up to date by the factory when the element moves.
+    position: Rc<RefCell<<C::ParentWidget as FactoryView>::Position>>,
+    returned_widget: Rc<RefCell<<C::ParentWidget as FactoryView>::ReturnedWidget>>,
+    output: Sender<C::Output>,
+}
+
+impl<C: FactoryComponent> PanicIsolation<C> {
+    /// Runs an update unless the component panicked before.
+    fn run(&mut self, model: &mut C, update: impl FnOnce(&mut C)) {
+        if self.failed.get() {
+            return;
+        }
+
+        if let Err(payload) = panic::catch_unwind(AssertUnwindSafe(|| update(model))) {
+            self.fail(model, payload);
+        }
+    }
+
+    /// Marks the component as failed, reports the panic and shows the placeholder.
+    fn fail(&mut self, model: &C, payload: Box<dyn Any + Send>) {
+        let message = panic_message(payload);
+        tracing::error!(
+            "Factory component {} panicked: {message}",
+            any::type_name::<C>()
+        );
+        self.failed.set(true);
+
+        // Both hooks run on the model that just panicked, so they might panic as well.
+        if let Some(output) =
+            catch_hook_panic::<C, _>("panic_output", || model.panic_output(&message))
+        {
+            self.output.send(output).ok();
+        }
+
+        if let Some(placeholder) =
+            catch_hook_panic::<C, _>("panic_placeholder", || model.panic_placeholder())
+        {
+            let mut returned_widget = self.returned_widget.borrow_mut();
+            let placeholder = self.parent.factory_insert_after(
+                placeholder,
+                &self.position.borrow(),
+                &returned_widget,
+            );
+            self.parent.factory_remove(&returned_widget);
+            *returned_widget = placeholder;
+        }
+    }
+}
+
+/// Runs a panic hook of a factory component and falls back to [`None`] if it panics as well.
+fn catch_hook_panic<C, T>(hook: &str, f: impl FnOnce() -> Option<T>) -> Option<T> {
+    panic::catch_unwind(AssertUnwindSafe(f)).unwrap_or_else(|payload| {
+        tracing::error!(
+            "{hook} of factory component {} panicked: {}",
+            any::type_name::<C>(),
+            panic_message(payload)
+        );
+        None
+    })
+}
+
+#[cfg(test)]
+mod test {
+    use std::time::Duration;
+
+    use gtk::glib;
+    use gtk::prelude::{Cast, GridExt, GtkWindowExt, ObjectExt, WidgetExt};
+
+    use crate::factory::positions::GridPosition;
+    use crate::factory::{
+        DynamicIndex, FactoryComponent, FactoryHashMap, FactorySender, FactoryVecDeque, Position,
+    };
+    use crate::testing::fixtures::{show, ViewCounter};
+    use crate::RelmIterChildrenExt;
+
+    #[derive(Debug)]
+    enum Output {
+        Panicked(usize, String),
+    }
+
+    struct Element {
+        index: DynamicIndex,
+        updates: u8,
+        // Lets the panic hooks panic as well.
+        unstable: bool,
+    }
+
+    impl FactoryComponent for Element {
+        type ParentWidget = gtk::Box;
+        type CommandOutput = ();
+        type Input = bool;
+        type Output = Output;
+        type Init = bool;
+        type Root = gtk::Label;
+        type Widgets = ();
+        type Index = DynamicIndex;
+
+        fn init_model(
+            unstable: Self::Init,
+            index: &DynamicIndex,
+            _sender: FactorySender<Self>,
+        ) -> Self {
+            Self {
+                index: index.clone(),
+                updates: 0,
+                unstable,
+            }
+        }
+
+        fn init_root(&self) -> Self::Root {
+            gtk::Label::default()
+        }
+
+        fn init_widgets(
+            &mut self,
+            _index: &DynamicIndex,
+            _root: Self::Root,
+            _returned_widget: &gtk::Widget,
+            _sender: FactorySender<Self>,
+        ) -> Self::Widgets {
+        }
+
+        fn update(&mut self, panic: Self::Input, _sender: FactorySender<Self>) {
+            assert!(!panic, "element panicked");
+            self.updates += 1;
+        }
+
+        fn panic_output(&self, message: &str) -> Option<Self::Output> {
+            assert!(!self.unstable, "panic_output panicked");
+            Some(Output::Panicked(self.index.current_index(), message.into()))
+        }
+
+        fn panic_placeholder(&self) -> Option<gtk::Widget> {
+            assert!(!self.unstable, "panic_placeholder panicked");
+            Some(gtk::Spinner::default().upcast())
+        }
+    }
+
+    #[gtk::test]
+    fn isolate_panics() {
+        let (sender, receiver) = crate::channel();
+        let mut factory = FactoryVecDeque::<Element>::builder()
+            .launch_default()
+            .forward(&sender, |output| output);
+        {
+            let mut guard = factory.guard();
+            guard.push_back(false);
+            guard.push_back(false);
+        }
+
+        factory.send(0, true);
+        factory.send(0, false);
+        factory.send(1, false);
+        let context = glib::MainContext::default();
+        while context.iteration(false) {}
+
+        assert!(factory.is_failed(0));
+        assert!(!factory.is_failed(1));
+        assert_eq!(factory[0].updates, 0);
+        assert_eq!(factory[1].updates, 1);
+
+        let children: Vec<_> = factory.widget().iter_children().collect();
+        assert_eq!(children.len(), 2);
+        assert!(children[0].is::<gtk::Spinner>());
+
+        let output = receiver.0.recv_timeout(Duration::from_secs(1)).unwrap();
+        assert!(matches!(output, Output::Panicked(0, message) if message == "element panicked"));
+
+        factory.guard().clear();
+        assert!(factory.widget().iter_children().next().is_none());
+    }
+
+    #[gtk::test]
+    fn isolate_panics_in_panic_hooks() {
+        let (sender, receiver) = crate::channel();
+        let mut factory = FactoryVecDeque::<Element>::builder()
+            .launch_default()
+            .forward(&sender, |output| output);
+        {
+            let mut guard = factory.guard();
+            guard.push_back(true);
+            guard.push_back(false);
+        }
+
+        factory.send(0, true);
+        factory.send(0, false);
+        factory.send(1, false);
+        let context = glib::MainContext::default();
+        while context.iteration(false) {}
+
+        assert!(factory.is_failed(0));
+        assert_eq!(factory[1].updates, 1);
+
+        // Without a placeholder, the widget of the element stays in place.
+        let children: Vec<_> = factory.widget().iter_children().collect();
+        assert_eq!(children.len(), 2);
+        assert!(children[0].is::<gtk::Label>());
+        assert!(receiver.0.try_recv().is_err());
+    }
+
+    // Placed in the row of its index.
+    struct GridCell;
+
+    impl Position<GridPosition, DynamicIndex> for GridCell {
+        fn position(&self, index: &DynamicIndex) -> GridPosition {
+            GridPosition {
+                column: 0,
+                row: index.current_index() as i32,
+                width: 1,
+                height: 1,
+            }
+        }
+    }
+
+    impl FactoryComponent for GridCell {
+        type ParentWidget = gtk::Grid;
+        type CommandOutput = ();
+        type Input = ();
+        type Output = ();
+        type Init = ();
+        type Root = gtk::Label;
+        type Widgets = ();
+        type Index = DynamicIndex;
+
+        fn init_model(
+            _init: Self::Init,
+            _index: &DynamicIndex,
+            _sender: FactorySender<Self>,
+        ) -> Self {
+            Self
+        }
+
+        fn init_root(&self) -> Self::Root {
+            gtk::Label::default()
+        }
+
+        fn init_widgets(
+            &mut self,
+            _index: &DynamicIndex,
+            _root: Self::Root,
+            _returned_widget: &gtk::Widget,
+            _sender: FactorySender<Self>,
+        ) -> Self::Widgets {
         }
+
+        fn update(&mut self, _message: Self::Input, _sender: FactorySender<Self>) {
+            panic!("cell panicked");
+        }
+
+        fn panic_placeholder(&self) -> Option<gtk::Widget> {
+            Some(gtk::Spinner::default().upcast())
+        }
+    }
+
+    #[gtk::test]
+    fn placeholder_at_current_position() {
+        let mut factory = FactoryVecDeque::<GridCell>::builder()
+            .launch_default()
+            .detach();
+        {
+            let mut guard = factory.guard();
+            guard.push_back(());
+            guard.push_back(());
+        }
+        // Moves the second cell to the first row.
+        factory.guard().pop_front();
+
+        factory.send(0, ());
+        let context = glib::MainContext::default();
+        while context.iteration(false) {}
+
+        assert!(factory.is_failed(0));
+        let placeholder = factory.widget().child_at(0, 0).unwrap();
+        assert!(placeholder.is::<gtk::Spinner>());
+        assert!(factory.widget().child_at(0, 1).is_none());
+    }
+
+    /// Hides the only element of `parent`, sends it two inputs and shows it again.
+    fn check_suspended(parent: &gtk::Box, send: impl Fn(), tile: impl Fn() -> (u8, u8)) {
+        let window = show(parent);
+        let context = glib::MainContext::default();
+        let root = parent.first_child().unwrap();
+
+        send();
+        while context.iteration(false) {}
+        assert_eq!(tile(), (1, 1));
+
+        // Only the model is updated while the root is hidden.
+        root.set_visible(false);
+        send();
+        send();
+        while context.iteration(false) {}
+        assert_eq!(tile(), (3, 1));
+
+        // The view is updated once it's visible again.
+        root.set_visible(true);
+        while context.iteration(false) {}
+        assert_eq!(tile(), (3, 2));
+
+        window.destroy();
+    }
+
+    #[gtk::test]
+    fn suspend_hidden_elements() {
+        let mut factory = FactoryVecDeque::<ViewCounter<DynamicIndex>>::builder()
+            .suspend_when_hidden()
+            .launch_default()
+            .detach();
+        factory.guard().push_back(());
+
+        check_suspended(
+            factory.widget(),
+            || factory.send(0, ()),
+            || (factory[0].value, factory[0].views.get()),
+        );
+    }
+
+    #[gtk::test]
+    fn suspend_hidden_map_elements() {
+        let mut factory = FactoryHashMap::<u8, ViewCounter<u8>>::builder()
+            .suspend_when_hidden()
+            .launch_default()
+            .detach();
+        factory.insert(0, ());
+
+        let tile = || factory.get(&0).unwrap();
+        check_suspended(
+            factory.widget(),
+            || factory.send(&0, ()),
+            || (tile().value, tile().views.get()),
+        );
     }
 }
@@ -1,11 +1,13 @@
+use crate::inspector;
 use crate::{Receiver, Sender};
 
-use crate::factory::sync::builder::FactoryBuilder;
+use crate::factory::sync::builder::{ElementOptions, FactoryBuilder};
 use crate::factory::sync::handle::FactoryHandle;
 use crate::factory::{CloneableFactoryComponent, FactoryComponent, FactoryView};
 
 use std::collections::hash_map::RandomState;
 use std::collections::HashMap;
+use std::fmt::Debug;
 use std::hash::{BuildHasher, Hash, Hasher};
 use std::iter::FusedIterator;
 use std::marker::PhantomData;
@@ -55,6 +57,7 @@ where
 /// A builder-pattern struct for building a [`FactoryHashMap`].
 pub struct FactoryHashMapBuilder<K, C: FactoryComponent, S = RandomState> {
     hasher: S,
+    options: ElementOptions<C>,
     component: PhantomData<C>,
     key: PhantomData<K>,
 }
@@ -89,6 +92,7 @@ where
     pub fn new() -> Self {
         Self {
             hasher: RandomState::default(),
+            options: ElementOptions::default(),
             component: PhantomData,
             key: PhantomData,
         }
@@ -96,19 +100,39 @@ where
 
     /// Sets a different hasher.
     pub fn hasher<H: Hasher>(self, hasher: H) -> FactoryHashMapBuilder<K, C, H> {
-        let Self { component, key, .. } = self;
+        let Self {
+            options,
+            component,
+            key,
+            ..
+        } = self;
 
         FactoryHashMapBuilder {
             hasher,
+            options,
             component,
             key,
         }
     }
 
+    /// Process all queued inputs of an element before updating its view.
+    ///
+    /// See [`ComponentBuilder::batch_inputs()`](crate::ComponentBuilder::batch_inputs) for details.
+    #[must_use]
+    pub fn batch_inputs(mut self) -> Self {
+        self.options.batch_inputs = true;
+        self
+    }
+
     /// Launch the factory.
     /// This is similar to [`Connector::launch`](crate::component::ComponentBuilder::launch).
     pub fn launch(self, widget: C::ParentWidget) -> FactoryHashMapConnector<K, C> {
-        let Self { hasher, key, .. } = self;
+        let Self {
+            hasher,
+            options,
+            key,
+            ..
+        } = self;
 
         let (output_sender, output_receiver) = crate::channel();
 
@@ -117,11 +141,39 @@ where
             output_sender,
             output_receiver,
             hasher,
+            options,
             _key: key,
         }
     }
 }
 
+impl<K, C, S> FactoryHashMapBuilder<K, C, S>
+where
+    C: FactoryComponent,
+    C::Root: AsRef<gtk::Widget>,
+{
+    /// Skip view updates of elements while their root widget is unmapped.
+    ///
+    /// See [`ComponentBuilder::suspend_when_hidden()`](crate::ComponentBuilder::suspend_when_hidden) for details.
+    #[must_use]
+    pub fn suspend_when_hidden(mut self) -> Self {
+        self.options.suspend = Some(AsRef::as_ref);
+        self
+    }
+}
+
+impl<K, C, S> FactoryHashMapBuilder<K, C, S>
+where
+    C: FactoryComponent + Debug,
+{
+    /// Show a [`Debug`] view of the models of the elements in the [`inspector`](crate::inspector).
+    #[must_use]
+    pub fn inspect(mut self) -> Self {
+        self.options.debug_model = Some(inspector::debug_model);
+        self
+    }
+}
+
 #[derive(Debug)]
 /// Second stage of the builder-pattern for building a [`FactoryHashMap`].
 pub struct FactoryHashMapConnector<K, C, S = RandomState>
@@ -132,6 +184,7 @@ where
     output_sender: Sender<C::Output>,
     output_receiver: Receiver<C::Output>,
     hasher: S,
+    options: ElementOptions<C>,
     _key: PhantomData<K>,
 }
 
@@ -151,6 +204,7 @@ where
             output_sender,
             output_receiver,
             hasher,
+            options,
             ..
         } = self;
 
@@ -168,6 +222,7 @@ where
             widget,
             output_sender,
             inner: HashMap::with_hasher(hasher),
+            options,
         }
     }
 
@@ -177,6 +232,7 @@ where
             widget,
             output_sender,
             hasher,
+            options,
             ..
         } = self;
 
@@ -184,6 +240,7 @@ where
             widget,
             output_sender,
             inner: HashMap::with_hasher(hasher),
+            options,
         }
     }
 }
@@ -195,6 +252,7 @@ pub struct FactoryHashMap<K, C: FactoryComponent, S = RandomState> {
     widget: C::ParentWidget,
     output_sender: Sender<C::Output>,
     inner: HashMap<K, FactoryHandle<C>, S>,
+    options: ElementOptions<C>,
 }
 
 impl<K, C, S> Drop for FactoryHashMap<K, C, S>
@@ -275,7 +333,7 @@ where
     /// Clears the map, removing all factory components.
     pub fn clear(&mut self) {
         for (_, handle) in self.inner.drain() {
-            self.widget.factory_remove(&handle.returned_widget);
+            self.widget.factory_remove(&handle.returned_widget.borrow());
         }
     }
 }
@@ -306,6 +364,15 @@ where
         self.inner[key].input.emit(msg);
     }
 
+    /// Returns true if the element of `key` panicked.
+    ///
+    /// Returns false if `key` is invalid.
+    ///
+    /// See [`FactoryComponent::panic_output()`] for details.
+    pub fn is_failed(&self, key: &K) -> bool {
+        self.inner.get(key).is_some_and(|c| c.failed.get())
+    }
+
     /// Tries to get an immutable reference to
     /// the model of one element.
     ///
@@ -333,14 +400,14 @@ where
     pub fn insert(&mut self, key: K, init: C::Init) -> Option<C> {
         let existing = self.remove(&key);
 
-        let builder = FactoryBuilder::new(&key, init, self.output_sender.clone());
+        let builder = FactoryBuilder::new(&key, init, self.output_sender.clone(), self.options);
 
         let position = C::position(&builder.data, &key);
         let returned_widget = self
             .widget
             .factory_append(builder.root_widget.clone(), &position);
 
-        let component = builder.launch(&key, returned_widget);
+        let component = builder.launch(&key, &self.widget, position, returned_widget);
 
         assert!(self.inner.insert(key, component).is_none());
 
@@ -350,7 +417,7 @@ where
     /// Removes a key from the map, returning the factory component at the key if the key was previously in the map.
     pub fn remove(&mut self, key: &K) -> Option<C> {
         if let Some(handle) = self.inner.remove(key) {
-            self.widget.factory_remove(&handle.returned_widget);
+            self.widget.factory_remove(&handle.returned_widget.borrow());
             Some(handle.data.into_inner())
         } else {
             None
@@ -367,9 +434,9 @@ where
 {
     fn clone(&self) -> Self {
         // Create a new, empty FactoryHashMap.
-        let mut clone = FactoryHashMap::builder()
-            .launch(self.widget.clone())
-            .detach();
+        let mut builder = FactoryHashMap::builder();
+        builder.options = self.options;
+        let mut clone = builder.launch(self.widget.clone()).detach();
         // Iterate over the items in the original FactoryHashMap.
         for (k, item) in self.iter() {
             // Clone each item and push it onto the new FactoryHashMap.
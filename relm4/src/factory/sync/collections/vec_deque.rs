@@ -1,6 +1,7 @@
+use crate::inspector;
 use crate::{Receiver, Sender};
 
-use crate::factory::sync::builder::FactoryBuilder;
+use crate::factory::sync::builder::{ElementOptions, FactoryBuilder};
 use crate::factory::sync::component_storage::ComponentStorage;
 use crate::factory::sync::traits::CloneableFactoryComponent;
 use crate::factory::{DynamicIndex, FactoryComponent, FactoryView};
@@ -9,6 +10,7 @@ use super::{ModelStateValue, RenderedState};
 
 use std::collections::hash_map::DefaultHasher;
 use std::collections::VecDeque;
+use std::fmt::Debug;
 use std::hash::Hash;
 use std::iter::FusedIterator;
 use std::marker::PhantomData;
@@ -179,8 +181,8 @@ where
         }
 
         if let Some(comp) = &component {
-            if let Some(widget) = &comp.returned_widget() {
-                self.widget.factory_remove(widget);
+            if let Some(widget) = comp.returned_widget() {
+                self.widget.factory_remove(&widget);
             }
         }
 
@@ -215,7 +217,12 @@ where
             states.index.increment();
         }
 
-        let builder = FactoryBuilder::new(&dyn_index, init, self.output_sender.clone());
+        let builder = FactoryBuilder::new(
+            &dyn_index,
+            init,
+            self.output_sender.clone(),
+            self.inner.options,
+        );
 
         self.inner
             .components
@@ -329,7 +336,7 @@ where
         for component in self.inner.components.drain(..) {
             // Remove all widgets
             if let Some(widget) = component.returned_widget() {
-                self.inner.widget.factory_remove(widget);
+                self.inner.widget.factory_remove(&widget);
             }
 
             // Make sure the component is shutdown properly
@@ -393,6 +400,7 @@ pub struct FactoryVecDequeBuilder<C>
 where
     C: FactoryComponent<Index = DynamicIndex>,
 {
+    options: ElementOptions<C>,
     _component: PhantomData<C>,
 }
 
@@ -425,10 +433,20 @@ where
     #[must_use]
     pub fn new() -> Self {
         Self {
+            options: ElementOptions::default(),
             _component: PhantomData,
         }
     }
 
+    /// Process all queued inputs of an element before updating its view.
+    ///
+    /// See [`ComponentBuilder::batch_inputs()`](crate::ComponentBuilder::batch_inputs) for details.
+    #[must_use]
+    pub fn batch_inputs(mut self) -> Self {
+        self.options.batch_inputs = true;
+        self
+    }
+
     /// Launch the factory.
     /// This is similar to [`Connector::launch`](crate::component::ComponentBuilder::launch).
     pub fn launch(self, widget: C::ParentWidget) -> FactoryVecDequeConnector<C> {
@@ -438,10 +456,38 @@ where
             widget,
             output_sender,
             output_receiver,
+            options: self.options,
         }
     }
 }
 
+impl<C> FactoryVecDequeBuilder<C>
+where
+    C: FactoryComponent<Index = DynamicIndex>,
+    C::Root: AsRef<gtk::Widget>,
+{
+    /// Skip view updates of elements while their root widget is unmapped.
+    ///
+    /// See [`ComponentBuilder::suspend_when_hidden()`](crate::ComponentBuilder::suspend_when_hidden) for details.
+    #[must_use]
+    pub fn suspend_when_hidden(mut self) -> Self {
+        self.options.suspend = Some(AsRef::as_ref);
+        self
+    }
+}
+
+impl<C> FactoryVecDequeBuilder<C>
+where
+    C: FactoryComponent<Index = DynamicIndex> + Debug,
+{
+    /// Show a [`Debug`] view of the models of the elements in the [`inspector`](crate::inspector).
+    #[must_use]
+    pub fn inspect(mut self) -> Self {
+        self.options.debug_model = Some(inspector::debug_model);
+        self
+    }
+}
+
 #[derive(Debug)]
 /// Second stage of the builder-pattern for building a [`FactoryVecDeque`].
 pub struct FactoryVecDequeConnector<C>
@@ -451,6 +497,7 @@ where
     widget: C::ParentWidget,
     output_sender: Sender<C::Output>,
     output_receiver: Receiver<C::Output>,
+    options: ElementOptions<C>,
 }
 
 impl<C> FactoryVecDequeConnector<C>
@@ -468,6 +515,7 @@ where
             widget,
             output_sender,
             output_receiver,
+            options,
         } = self;
 
         let sender_clone = sender_.clone();
@@ -487,6 +535,7 @@ where
             rendered_state: VecDeque::new(),
             // 0 is always an invalid uid
             uid_counter: 1,
+            options,
         }
     }
 
@@ -495,6 +544,7 @@ where
         let Self {
             widget,
             output_sender,
+            options,
             ..
         } = self;
         FactoryVecDeque {
@@ -505,6 +555,7 @@ where
             rendered_state: VecDeque::new(),
             // 0 is always an invalid uid
             uid_counter: 1,
+            options,
         }
     }
 }
@@ -524,6 +575,7 @@ where
     model_state: VecDeque<ModelStateValue>,
     rendered_state: VecDeque<RenderedState>,
     uid_counter: usize,
+    options: ElementOptions<C>,
 }
 
 impl<C> Drop for FactoryVecDeque<C>
@@ -598,10 +650,10 @@ where
                 // Detach and re-attach item
                 let widget = components[index].returned_widget().unwrap();
                 if index == 0 {
-                    self.widget.factory_move_start(widget);
+                    self.widget.factory_move_start(&widget);
                 } else {
                     let previous_widget = components[index - 1].returned_widget().unwrap();
-                    self.widget.factory_move_after(widget, previous_widget);
+                    self.widget.factory_move_after(&widget, &previous_widget);
                 }
 
                 if state.changed {
@@ -622,11 +674,13 @@ where
                 } else {
                     let previous_widget = components[index - 1].returned_widget().unwrap();
                     self.widget
-                        .factory_insert_after(insert_widget, &position, previous_widget)
+                        .factory_insert_after(insert_widget, &position, &previous_widget)
                 };
                 let component = components.remove(index).unwrap();
                 let dyn_index = &self.model_state[index].index;
-                let component = component.launch(dyn_index, returned_widget).unwrap();
+                let component = component
+                    .launch(dyn_index, &self.widget, position, returned_widget)
+                    .unwrap();
                 components.insert(index, component);
             }
         }
@@ -656,7 +710,8 @@ where
             for (index, comp) in components.iter().enumerate().skip(change_index) {
                 let position = C::position(comp.get(), &self.model_state[index].index);
                 self.widget
-                    .factory_update_position(comp.returned_widget().unwrap(), &position);
+                    .factory_update_position(&comp.returned_widget().unwrap(), &position);
+                comp.set_position(position);
             }
         }
     }
@@ -684,6 +739,13 @@ where
         self.components.iter().for_each(|c| c.send(msg.clone()));
     }
 
+    /// Returns true if the element at `index` panicked.
+    ///
+    /// See [`FactoryComponent::panic_output()`] for details.
+    pub fn is_failed(&self, index: usize) -> bool {
+        self.components[index].is_failed()
+    }
+
     /// Tries to get an immutable reference to
     /// the model of one element.
     ///
@@ -753,9 +815,9 @@ where
 {
     fn clone(&self) -> Self {
         // Create a new, empty FactoryVecDeque.
-        let mut clone = FactoryVecDeque::builder()
-            .launch(self.widget.clone())
-            .detach();
+        let mut builder = FactoryVecDeque::builder();
+        builder.options = self.options;
+        let mut clone = builder.launch(self.widget.clone()).detach();
         // Iterate over the items in the original FactoryVecDeque.
         for item in self.iter() {
             // Clone each item and push it onto the new FactoryVecDeque.
@@ -1,3 +1,5 @@
+use std::cell::Ref;
+
 use crate::factory::{FactoryComponent, FactoryView};
 
 use super::{FactoryBuilder, FactoryHandle};
@@ -53,22 +55,43 @@ impl<C: FactoryComponent> ComponentStorage<C> {
     pub(super) fn launch(
         self,
         index: &C::Index,
+        parent: &C::ParentWidget,
+        position: <C::ParentWidget as FactoryView>::Position,
         returned_widget: <C::ParentWidget as FactoryView>::ReturnedWidget,
     ) -> Option<Self> {
         if let Self::Builder(builder) = self {
-            Some(Self::Final(builder.launch(index, returned_widget)))
+            Some(Self::Final(builder.launch(
+                index,
+                parent,
+                position,
+                returned_widget,
+            )))
         } else {
             None
         }
     }
 
-    pub(super) const fn returned_widget(
+    pub(super) fn returned_widget(
         &self,
-    ) -> Option<&<C::ParentWidget as FactoryView>::ReturnedWidget> {
+    ) -> Option<Ref<'_, <C::ParentWidget as FactoryView>::ReturnedWidget>> {
         if let Self::Final(handle) = self {
-            Some(&handle.returned_widget)
+            Some(handle.returned_widget.borrow())
         } else {
             None
         }
     }
+
+    /// Stores the position after the element was moved.
+    pub(super) fn set_position(&self, position: <C::ParentWidget as FactoryView>::Position) {
+        if let Self::Final(handle) = self {
+            *handle.position.borrow_mut() = position;
+        }
+    }
+
+    pub(super) fn is_failed(&self) -> bool {
+        match self {
+            Self::Builder(_) => false,
+            Self::Final(handle) => handle.failed.get(),
+        }
+    }
 }
@@ -1,4 +1,6 @@
+use std::cell::{Cell, RefCell};
 use std::fmt;
+use std::rc::Rc;
 
 use crate::factory::{DataGuard, FactoryComponent, FactoryView};
 use crate::Sender;
@@ -11,9 +13,13 @@ use crate::Sender;
 pub(super) struct FactoryHandle<C: FactoryComponent> {
     pub(super) data: DataGuard<C, C::Widgets, C::Output>,
     pub(super) root_widget: C::Root,
-    pub(super) returned_widget: <C::ParentWidget as FactoryView>::ReturnedWidget,
+    // Replaced by the placeholder if the component panicked.
+    pub(super) returned_widget: Rc<RefCell<<C::ParentWidget as FactoryView>::ReturnedWidget>>,
+    // The current position, where the placeholder is inserted if the component panics.
+    pub(super) position: Rc<RefCell<<C::ParentWidget as FactoryView>::Position>>,
     pub(super) input: Sender<C::Input>,
     pub(super) notifier: Sender<()>,
+    pub(super) failed: Rc<Cell<bool>>,
 }
 
 impl<C: FactoryComponent> fmt::Debug for FactoryHandle<C> {
@@ -23,6 +29,7 @@ impl<C: FactoryComponent> fmt::Debug for FactoryHandle<C> {
             .field("root_widget", &self.root_widget)
             .field("input", &self.input)
             .field("notifier", &self.notifier)
+            .field("failed", &self.failed)
             .finish()
     }
 }
@@ -90,9 +90,38 @@ pub trait FactoryComponent:
     }
 
     /// Last method called before a component is shut down.
+    ///
+    /// This method isn't called if the component panicked before.
     #[allow(unused)]
     fn shutdown(&mut self, widgets: &mut Self::Widgets, output: Sender<Self::Output>) {}
 
+    /// Reports a panic of the component to the parent.
+    ///
+    /// If a factory component panics while processing a message, the panic is caught
+    /// and the component is marked as failed. It doesn't receive any further messages,
+    /// but the other elements of the factory keep working.
+    ///
+    /// The returned output is passed to the `forward` mapping of the factory.
+    /// Because the panic might have left the model in an inconsistent state,
+    /// only read what's needed to identify the element, like its index.
+    /// If this method panics as well, no output is sent.
+    ///
+    /// Panics in [`init_model()`](Self::init_model), [`init_root()`](Self::init_root)
+    /// and [`init_widgets()`](Self::init_widgets) aren't caught. They unwind
+    /// through the code that added or rendered the element.
+    #[allow(unused)]
+    fn panic_output(&self, message: &str) -> Option<Self::Output> {
+        None
+    }
+
+    /// Creates a widget that replaces the widgets of the component after it panicked.
+    ///
+    /// The default implementation keeps the widgets of the component in place,
+    /// which is also what happens if this method panics.
+    fn panic_placeholder(&self) -> Option<<Self::ParentWidget as FactoryView>::Children> {
+        None
+    }
+
     /// An identifier for the component used for debug logging.
     ///
     /// The default implementation of this method uses the address of the component, but
@@ -0,0 +1,326 @@
+//! Inspect the components and factories that are currently running.
+//!
+//! Similar to the interactive debugger of GTK, the [inspector window](window)
+//! shows all live components of the current thread together with their type name,
+//! [`id()`](crate::Component::id), the number of queued inputs and,
+//! if enabled, a [`Debug`] view of their model.
+//!
+//! Inspecting components has a small runtime cost, so it needs to be
+//! turned on explicitly with [`enable()`] or by creating the inspector window.
+//! Only components launched afterwards are tracked.
+//!
+//! The model of a component is only shown if the component was launched with
+//! [`ComponentBuilder::inspect()`](crate::ComponentBuilder::inspect) or
+//! [`AsyncComponentBuilder::inspect()`](crate::component::AsyncComponentBuilder::inspect).
+//! It is formatted after every update, which can be slow for large models.
+//!
+//! ```no_run
+//! # use relm4::RelmApp;
+//! # type App = ();
+//! let app = RelmApp::new("relm4.example.inspector");
+//! relm4::inspector::enable();
+//! # app.run::<App>(());
+//! ```
+//!
+//! Inside the app, call [`window()`] and present the window.
+
+mod window;
+
+pub use window::window;
+
+use std::any;
+use std::cell::{Cell, RefCell};
+use std::fmt::{self, Debug};
+use std::rc::{Rc, Weak};
+
+use crate::Sender;
+
+thread_local! {
+    // `None` while the inspector is disabled.
+    static REGISTRY: RefCell<Option<Vec<Weak<dyn Entry>>>> = RefCell::default();
+    static NEXT_SERIAL: Cell<u64> = Cell::default();
+}
+
+/// Starts tracking all components and factories launched on the current thread from now on.
+pub fn enable() {
+    REGISTRY.with(|registry| {
+        registry.borrow_mut().get_or_insert_with(Vec::new);
+    });
+}
+
+/// Returns [`true`] if components launched on the current thread are tracked.
+#[must_use]
+pub fn is_enabled() -> bool {
+    REGISTRY.with(|registry| registry.borrow().is_some())
+}
+
+/// Returns information about all live components of the current thread
+/// in the order they were launched.
+///
+/// This is empty unless the inspector was [enabled](enable).
+#[must_use]
+pub fn components() -> Vec<ComponentInfo> {
+    REGISTRY.with(|registry| {
+        let mut registry = registry.borrow_mut();
+        let Some(entries) = registry.as_mut() else {
+            return Vec::new();
+        };
+
+        entries.retain(|entry| entry.strong_count() > 0);
+        entries
+            .iter()
+            .filter_map(Weak::upgrade)
+            .map(|entry| entry.info())
+            .collect()
+    })
+}
+
+/// The kind of an inspected component.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ComponentKind {
+    /// A [`Component`](crate::Component).
+    Component,
+    /// An [`AsyncComponent`](crate::component::AsyncComponent).
+    AsyncComponent,
+    /// A [`FactoryComponent`](crate::factory::FactoryComponent).
+    FactoryComponent,
+    /// An [`AsyncFactoryComponent`](crate::factory::AsyncFactoryComponent).
+    AsyncFactoryComponent,
+}
+
+/// A snapshot of the state of a live component.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ComponentInfo {
+    /// Identifies the component among all inspected components of the thread.
+    pub serial: u64,
+    /// The kind of the component.
+    pub kind: ComponentKind,
+    /// The type name of the component.
+    pub type_name: &'static str,
+    /// The [`id()`](crate::Component::id) of the component after its last update.
+    pub id: String,
+    /// The number of inputs that wait to be processed.
+    pub queue_depth: usize,
+    /// The [`Debug`] representation of the model after its last update,
+    /// if the component is inspectable.
+    pub model: Option<String>,
+}
+
+trait Entry {
+    fn info(&self) -> ComponentInfo;
+}
+
+/// Tracks a single component while it's running.
+///
+/// The runtime of the component owns this type, so the component
+/// disappears from the inspector as soon as its runtime stops.
+pub(crate) struct Inspection<C> {
+    serial: u64,
+    kind: ComponentKind,
+    queue_depth: Box<dyn Fn() -> usize>,
+    id_of: fn(&C) -> String,
+    debug_model: Option<fn(&C) -> String>,
+    id: RefCell<String>,
+    model: RefCell<Option<String>>,
+}
+
+impl<C: 'static> Inspection<C> {
+    /// Registers a component if the inspector is enabled.
+    pub(crate) fn register<I: 'static>(
+        kind: ComponentKind,
+        input: &Sender<I>,
+        id_of: fn(&C) -> String,
+        debug_model: Option<fn(&C) -> String>,
+    ) -> Option<Rc<Self>> {
+        REGISTRY.with(|registry| {
+            let mut registry = registry.borrow_mut();
+            let entries = registry.as_mut()?;
+
+            let serial = NEXT_SERIAL.with(|next| next.replace(next.get() + 1));
+            // Don't keep the input channel alive.
+            let input = input.0.downgrade();
+            let inspection = Rc::new(Self {
+                serial,
+                kind,
+                queue_depth: Box::new(move || input.upgrade().map_or(0, |input| input.len())),
+                id_of,
+                debug_model,
+                id: RefCell::default(),
+                model: RefCell::default(),
+            });
+
+            let entry: Weak<dyn Entry> = Rc::downgrade(&inspection) as Weak<Self>;
+            entries.push(entry);
+            Some(inspection)
+        })
+    }
+
+    /// Stores the current state of the model.
+    pub(crate) fn update(&self, model: &C) {
+        *self.id.borrow_mut() = (self.id_of)(model);
+        if let Some(debug_model) = self.debug_model {
+            *self.model.borrow_mut() = Some(debug_model(model));
+        }
+    }
+}
+
+impl<C> Entry for Inspection<C> {
+    fn info(&self) -> ComponentInfo {
+        ComponentInfo {
+            serial: self.serial,
+            kind: self.kind,
+            type_name: any::type_name::<C>(),
+            id: self.id.borrow().clone(),
+            queue_depth: (self.queue_depth)(),
+            model: self.model.borrow().clone(),
+        }
+    }
+}
+
+impl<C> Debug for Inspection<C> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Inspection")
+            .field("serial", &self.serial)
+            .field("kind", &self.kind)
+            .field("id", &self.id)
+            .finish_non_exhaustive()
+    }
+}
+
+/// Formats the model of an inspectable component.
+pub(crate) fn debug_model<C: Debug>(model: &C) -> String {
+    format!("{model:#?}")
+}
+
+#[cfg(test)]
+mod test {
+    use std::any;
+
+    use gtk::glib;
+
+    use super::{ComponentInfo, ComponentKind};
+    use crate::factory::{DynamicIndex, FactoryComponent, FactorySender, FactoryVecDeque};
+    use crate::testing::ComponentTester;
+    use crate::{Component, ComponentParts, ComponentSender, SimpleComponent};
+
+    /// Only returns components of type `C`.
+    ///
+    /// The registry is shared by all tests on the thread and components of
+    /// other tests might not have shut down yet.
+    fn components_of<C>() -> Vec<ComponentInfo> {
+        super::components()
+            .into_iter()
+            .filter(|info| info.type_name == any::type_name::<C>())
+            .collect()
+    }
+
+    #[derive(Debug)]
+    struct Counter(u8);
+
+    impl SimpleComponent for Counter {
+        type Input = ();
+        type Output = ();
+        type Init = ();
+        type Root = ();
+        type Widgets = ();
+
+        fn init_root() -> Self::Root {}
+
+        fn init(
+            _init: Self::Init,
+            _root: Self::Root,
+            _sender: ComponentSender<Self>,
+        ) -> ComponentParts<Self> {
+            ComponentParts {
+                model: Counter(0),
+                widgets: (),
+            }
+        }
+
+        fn update(&mut self, _message: Self::Input, _sender: ComponentSender<Self>) {
+            self.0 += 1;
+        }
+    }
+
+    #[gtk::test]
+    fn track_live_components() {
+        super::enable();
+
+        let mut tester = ComponentTester::from_builder(Counter::builder().inspect(), ());
+        tester.send(());
+
+        let components = components_of::<Counter>();
+        assert_eq!(components.len(), 1);
+        let info = &components[0];
+        assert_eq!(info.kind, ComponentKind::Component);
+        assert_eq!(info.id, tester.model().id());
+        assert_eq!(info.queue_depth, 0);
+        assert_eq!(info.model.as_deref(), Some("Counter(\n    1,\n)"));
+
+        assert!(tester.shutdown().is_empty());
+        assert!(components_of::<Counter>().is_empty());
+    }
+
+    #[derive(Debug)]
+    struct Element(u8);
+
+    impl FactoryComponent for Element {
+        type ParentWidget = gtk::Box;
+        type CommandOutput = ();
+        type Input = ();
+        type Output = ();
+        type Init = u8;
+        type Root = gtk::Label;
+        type Widgets = ();
+        type Index = DynamicIndex;
+
+        fn init_model(
+            init: Self::Init,
+            _index: &DynamicIndex,
+            _sender: FactorySender<Self>,
+        ) -> Self {
+            Self(init)
+        }
+
+        fn init_root(&self) -> Self::Root {
+            gtk::Label::default()
+        }
+
+        fn init_widgets(
+            &mut self,
+            _index: &DynamicIndex,
+            _root: Self::Root,
+            _returned_widget: &gtk::Widget,
+            _sender: FactorySender<Self>,
+        ) -> Self::Widgets {
+        }
+
+        fn update(&mut self, _message: Self::Input, _sender: FactorySender<Self>) {
+            self.0 += 1;
+        }
+    }
+
+    #[gtk::test]
+    fn inspect_factory_elements() {
+        super::enable();
+
+        let mut factory = FactoryVecDeque::<Element>::builder()
+            .inspect()
+            .launch_default()
+            .detach();
+        factory.guard().push_back(4);
+        factory.send(0, ());
+        let context = glib::MainContext::default();
+        while context.iteration(false) {}
+
+        let components = components_of::<Element>();
+        assert_eq!(components.len(), 1);
+        let info = &components[0];
+        assert_eq!(info.kind, ComponentKind::FactoryComponent);
+        assert_eq!(info.model.as_deref(), Some("Element(\n    5,\n)"));
+
+        factory.guard().clear();
+        while context.iteration(false) {}
+        assert!(components_of::<Element>().is_empty());
+    }
+}
@@ -0,0 +1,125 @@
+use std::cell::RefCell;
+use std::collections::HashMap;
+use std::rc::Rc;
+use std::time::Duration;
+
+use gtk::glib;
+use gtk::prelude::ObjectExt;
+
+use super::ComponentInfo;
+
+/// How often the inspector window refreshes its content.
+const REFRESH_INTERVAL: Duration = Duration::from_millis(500);
+
+struct Row {
+    row: gtk::ListBoxRow,
+    expander: gtk::Expander,
+    model: gtk::Label,
+}
+
+/// Creates a window that lists all live components of the current thread
+/// and updates them continuously.
+///
+/// This [enables](super::enable) the inspector, but components that were
+/// launched before aren't shown.
+#[must_use]
+pub fn window() -> gtk::Window {
+    super::enable();
+
+    let list = gtk::ListBox::builder()
+        .selection_mode(gtk::SelectionMode::None)
+        .build();
+    let placeholder = gtk::Label::new(Some("No live components"));
+    list.set_placeholder(Some(&placeholder));
+
+    let scrolled_window = gtk::ScrolledWindow::builder()
+        .hscrollbar_policy(gtk::PolicyType::Never)
+        .child(&list)
+        .build();
+
+    let window = gtk::Window::builder()
+        .title("Relm4 Inspector")
+        .default_width(600)
+        .default_height(500)
+        .child(&scrolled_window)
+        .build();
+
+    let rows = Rc::new(RefCell::new(HashMap::new()));
+    refresh(&list, &mut rows.borrow_mut());
+
+    let list = list.downgrade();
+    glib::timeout_add_local(REFRESH_INTERVAL, move || {
+        let Some(list) = list.upgrade() else {
+            return glib::ControlFlow::Break;
+        };
+        refresh(&list, &mut rows.borrow_mut());
+        glib::ControlFlow::Continue
+    });
+
+    window
+}
+
+fn refresh(list: &gtk::ListBox, rows: &mut HashMap<u64, Row>) {
+    let components = super::components();
+
+    rows.retain(|serial, row| {
+        let alive = components.iter().any(|info| info.serial == *serial);
+        if !alive {
+            list.remove(&row.row);
+        }
+        alive
+    });
+
+    for info in &components {
+        let row = rows.entry(info.serial).or_insert_with(|| append_row(list));
+        update_row(row, info);
+    }
+}
+
+fn append_row(list: &gtk::ListBox) -> Row {
+    let model = gtk::Label::builder()
+        .xalign(0.0)
+        .selectable(true)
+        .wrap(true)
+        .margin_start(12)
+        .css_classes(["monospace"])
+        .build();
+
+    let expander = gtk::Expander::builder()
+        .child(&model)
+        .margin_top(6)
+        .margin_bottom(6)
+        .margin_start(6)
+        .margin_end(6)
+        .build();
+
+    let row = gtk::ListBoxRow::builder()
+        .activatable(false)
+        .child(&expander)
+        .build();
+    list.append(&row);
+
+    Row {
+        row,
+        expander,
+        model,
+    }
+}
+
+fn update_row(row: &Row, info: &ComponentInfo) {
+    let title = format!(
+        "{} ({:?})\nid: {}, queued inputs: {}",
+        info.type_name, info.kind, info.id, info.queue_depth
+    );
+    if row.expander.label().as_deref() != Some(title.as_str()) {
+        row.expander.set_label(Some(&title));
+    }
+
+    let model = info
+        .model
+        .as_deref()
+        .unwrap_or("The model isn't inspectable, launch the component with `inspect()`.");
+    if row.model.label() != model {
+        row.model.set_label(model);
+    }
+}
@@ -33,6 +33,7 @@
 
 mod app;
 mod channel;
+mod clock;
 mod extensions;
 pub(crate) mod late_initialization;
 mod runtime_util;
@@ -41,17 +42,23 @@ pub mod abstractions;
 pub mod actions;
 pub mod binding;
 pub mod component;
+pub mod executor;
 pub mod factory;
+pub mod inspector;
 pub mod loading_widgets;
 pub mod shared_state;
+pub mod testing;
 pub mod typed_view;
 
 pub use channel::ComponentSender;
 pub use channel::*;
-pub use component::worker::{Worker, WorkerController, WorkerHandle};
+pub use component::worker::{
+    Worker, WorkerController, WorkerHandle, WorkerPool, WorkerPoolHandle, WorkerPoolStream,
+    WorkerRuntime, WorkerStream,
+};
 pub use component::{
-    Component, ComponentBuilder, ComponentController, ComponentParts, Controller, MessageBroker,
-    SimpleComponent,
+    Children, Component, ComponentBuilder, ComponentController, ComponentParts, Controller,
+    MessageBroker, SimpleComponent,
 };
 pub use extensions::*;
 pub use shared_state::{Reducer, Reducible, SharedState};
@@ -8,7 +8,7 @@ use tokio::sync::mpsc;
 
 use crate::{
     shutdown::{self, ShutdownSender},
-    Receiver, Sender, ShutdownReceiver,
+    CommandReceiver, CommandSender, Receiver, Sender, ShutdownReceiver,
 };
 
 /// Stores the shutdown senders of all components ever created during
@@ -56,24 +56,24 @@ impl Drop for ShutdownOnDrop {
     }
 }
 
-pub(super) struct RuntimeSenders<Output, Command> {
+pub(super) struct RuntimeSenders<Output, Command: 'static> {
     pub(super) output_sender: Sender<Output>,
     pub(super) output_receiver: Receiver<Output>,
-    pub(super) cmd_sender: Sender<Command>,
-    pub(super) cmd_receiver: Receiver<Command>,
+    pub(super) cmd_sender: CommandSender<Command>,
+    pub(super) cmd_receiver: CommandReceiver<Command>,
     pub(super) shutdown_notifier: ShutdownSender,
     pub(super) shutdown_recipient: ShutdownReceiver,
     pub(super) shutdown_on_drop: ShutdownOnDrop,
     pub(super) shutdown_event: ShutdownEvent,
 }
 
-impl<Output, Command> RuntimeSenders<Output, Command> {
+impl<Output, Command: 'static> RuntimeSenders<Output, Command> {
     pub(super) fn new() -> Self {
         // Used by this component to send events to be handled externally by the caller.
         let (output_sender, output_receiver) = crate::channel::<Output>();
 
         // Sends messages from commands executed from the background.
-        let (cmd_sender, cmd_receiver) = crate::channel::<Command>();
+        let (cmd_sender, cmd_receiver) = crate::command_channel::<Command>();
 
         // Notifies the component's child commands that it is being shut down.
         let (shutdown_notifier, shutdown_recipient) = shutdown::channel();
@@ -181,6 +181,16 @@ where
             sender_dropped: false,
         }
     }
+
+    /// Returns the number of messages in the channel.
+    pub(super) fn len(&self) -> usize {
+        self.receive_stream.len()
+    }
+
+    /// Returns the capacity of the channel or [`None`] if it's unbounded.
+    pub(super) fn capacity(&self) -> Option<usize> {
+        self.receive_stream.capacity()
+    }
 }
 
 impl<T> Future for GuardedReceiver<'_, T>
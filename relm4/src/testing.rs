@@ -0,0 +1,724 @@
+//! Utilities for testing components without showing any windows.
+//!
+//! The [`ComponentTester`] launches a [`Component`] on the [`glib::MainContext`]
+//! of the current thread and processes its messages on demand.
+//! This allows tests to send inputs, inspect the model and check the
+//! emitted outputs without setting up a main loop by hand.
+//!
+//! ```
+//! use relm4::testing::ComponentTester;
+//!
+//! // An empty component, usually you'd test your own component here.
+//! let mut tester = ComponentTester::<()>::new(());
+//! tester.send(());
+//!
+//! assert!(tester.outputs().is_empty());
+//! ```
+
+use std::cell::Ref;
+use std::collections::VecDeque;
+use std::fmt::{self, Debug};
+use std::mem;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+use gtk::glib;
+
+use crate::clock::VirtualClock;
+use crate::component::{CommandFuture, ComponentMessage, RecordedMessage, Redirect};
+use crate::executor::{BlockingCommand, CommandExecutor};
+use crate::{
+    CommandHandle, CommandReceiver, Component, ComponentBuilder, ComponentController, Controller,
+    Receiver, Sender, RUNTIME,
+};
+
+/// Runs a [`Component`] in the background and gives tests full control
+/// over the messages it processes.
+///
+/// All outputs of the component are captured and can be retrieved with
+/// [`outputs()`](Self::outputs) or [`take_outputs()`](Self::take_outputs).
+///
+/// Unless the builder has its own [`command_executor()`](ComponentBuilder::command_executor),
+/// commands only run once the test calls [`run_commands()`](Self::run_commands).
+/// Their outputs are captured as well, but they are only passed to the
+/// component once the test calls [`deliver_command_output()`](Self::deliver_command_output)
+/// or [`deliver_command_outputs()`](Self::deliver_command_outputs).
+/// This way, tests decide when the results of background tasks such as
+/// network requests arrive, independent of how long they actually took.
+///
+/// Timers and delayed inputs of components on the thread of the tester use virtual time,
+/// which only passes when the test calls [`advance()`](Self::advance).
+///
+/// # Note
+///
+/// Components with widgets need an initialized GTK.
+/// Use [`gtk::test`] instead of the regular `test` attribute for those tests.
+pub struct ComponentTester<C: Component> {
+    controller: Controller<C>,
+    output_receiver: Receiver<C::Output>,
+    outputs: Vec<C::Output>,
+    command_receiver: CommandReceiver<C::CommandOutput>,
+    command_sender: Sender<C::CommandOutput>,
+    command_outputs: VecDeque<C::CommandOutput>,
+    // The commands of the captured outputs, if they were sent by one.
+    command_handles: VecDeque<Option<CommandHandle>>,
+    // Only set if the builder had no executor.
+    executor: Option<ManualExecutor>,
+    clock: Arc<VirtualClock>,
+    // Inputs the component sent to itself while replaying.
+    suppressed_inputs: Option<Receiver<C::Input>>,
+}
+
+impl<C: Component> ComponentTester<C> {
+    /// Launches the component with its default builder.
+    ///
+    /// All messages sent during initialization are processed before this returns.
+    #[must_use]
+    pub fn new(payload: C::Init) -> Self {
+        Self::from_builder(C::builder(), payload)
+    }
+
+    /// Launches the component from a custom [`ComponentBuilder`].
+    ///
+    /// All messages sent during initialization are processed before this returns.
+    #[must_use]
+    pub fn from_builder(builder: ComponentBuilder<C>, payload: C::Init) -> Self {
+        Self::launch(builder, payload, false)
+    }
+
+    /// Launches the component for replaying messages recorded by a
+    /// [`MessageRecorder`](crate::component::MessageRecorder).
+    ///
+    /// Use [`replay()`](Self::replay) to pass the recorded messages to the component.
+    ///
+    /// Recordings contain every message the component processed, including
+    /// the inputs it sent to itself. Therefore, inputs the component sends to itself
+    /// are discarded in this mode, so they aren't processed twice.
+    #[must_use]
+    pub fn replaying(builder: ComponentBuilder<C>, payload: C::Init) -> Self {
+        Self::launch(builder, payload, true)
+    }
+
+    fn launch(mut builder: ComponentBuilder<C>, payload: C::Init, replaying: bool) -> Self {
+        // Timers started during initialization already use the virtual clock.
+        let clock = VirtualClock::current();
+        let executor = builder.executor.is_none().then(|| {
+            let executor = ManualExecutor::new();
+            builder.executor = Some(Arc::new(executor.clone()));
+            executor
+        });
+
+        let (captured_command_sender, command_receiver) = crate::command_channel();
+        let (suppressed_input_sender, suppressed_inputs) = if replaying {
+            let (sender, receiver) = crate::channel();
+            (Some(sender), Some(receiver))
+        } else {
+            (None, None)
+        };
+
+        let redirect = Redirect {
+            input: suppressed_input_sender,
+            command: Some(captured_command_sender),
+            output: None,
+        };
+        let (connector, command_sender) = builder.launch_intercepted(payload, redirect);
+
+        let (output_sender, output_receiver) = crate::channel();
+        let controller = connector.forward(&output_sender, |output| output);
+
+        let mut tester = Self {
+            controller,
+            output_receiver,
+            outputs: Vec::new(),
+            command_receiver,
+            command_sender: command_sender.untagged().clone(),
+            command_outputs: VecDeque::new(),
+            command_handles: VecDeque::new(),
+            executor,
+            clock,
+            suppressed_inputs,
+        };
+        tester.process();
+        tester
+    }
+
+    /// Sends an input to the component and processes it.
+    pub fn send(&mut self, input: C::Input) {
+        self.controller.emit(input);
+        self.process();
+    }
+
+    /// Runs the main context until no more events are pending.
+    ///
+    /// This processes all queued inputs and collects outputs
+    /// and command outputs emitted in the meantime.
+    /// Captured outputs of commands that were aborted in the meantime are dropped.
+    pub fn process(&mut self) {
+        let context = glib::MainContext::ref_thread_default();
+        while context.iteration(false) {}
+
+        self.outputs.extend(self.output_receiver.0.try_iter());
+        for (handle, output) in self.command_receiver.drain_tagged() {
+            self.command_handles.push_back(handle);
+            self.command_outputs.push_back(output);
+        }
+        self.drop_aborted_command_outputs();
+        if let Some(receiver) = &self.suppressed_inputs {
+            receiver.0.drain();
+        }
+    }
+
+    /// Passes a recorded message to the component and processes it.
+    ///
+    /// Inputs and command outputs are processed in the order they were recorded.
+    /// Command outputs are delivered directly, so the outputs of the commands
+    /// the component spawns while replaying don't affect the result.
+    /// Recorded outputs are skipped, compare them with [`outputs()`](Self::outputs) instead.
+    ///
+    /// Usually, the component should be launched with [`replaying()`](Self::replaying).
+    pub fn replay(&mut self, message: RecordedMessage<C>) {
+        match message.message {
+            ComponentMessage::Input(input) => self.send(input),
+            ComponentMessage::CommandOutput(output) => {
+                self.command_sender.emit(output);
+                self.process();
+            }
+            ComponentMessage::Output(_) => {}
+        }
+    }
+
+    /// Returns a reference to the model of the component.
+    #[must_use]
+    pub fn model(&self) -> Ref<'_, C> {
+        self.controller.model()
+    }
+
+    /// Returns a reference to the widgets of the component.
+    #[must_use]
+    pub fn widgets(&self) -> Ref<'_, C::Widgets> {
+        self.controller.widgets()
+    }
+
+    /// Returns the root widget of the component.
+    #[must_use]
+    pub fn widget(&self) -> &C::Root {
+        self.controller.widget()
+    }
+
+    /// Provides access to the controller of the component.
+    #[must_use]
+    pub const fn controller(&self) -> &Controller<C> {
+        &self.controller
+    }
+
+    /// Returns all outputs captured so far.
+    #[must_use]
+    pub fn outputs(&self) -> &[C::Output] {
+        &self.outputs
+    }
+
+    /// Removes and returns all outputs captured so far.
+    pub fn take_outputs(&mut self) -> Vec<C::Output> {
+        mem::take(&mut self.outputs)
+    }
+
+    /// Returns all command outputs that were captured,
+    /// but not delivered to the component yet.
+    #[must_use]
+    pub const fn command_outputs(&self) -> &VecDeque<C::CommandOutput> {
+        &self.command_outputs
+    }
+
+    /// Runs all commands spawned so far on the current thread,
+    /// including the commands they spawn in turn, and captures their outputs.
+    ///
+    /// This does nothing if the builder has its own
+    /// [`command_executor()`](ComponentBuilder::command_executor).
+    /// See [`ManualExecutor`] for the limits of running commands this way.
+    pub fn run_commands(&mut self) {
+        if let Some(executor) = &self.executor {
+            executor.run_all();
+        }
+        self.process();
+    }
+
+    /// Advances the virtual time by `duration` and processes all timers
+    /// and delayed inputs that are due in the meantime, in the order of their deadlines.
+    pub fn advance(&mut self, duration: Duration) {
+        let target = self.clock.now() + duration;
+        self.process();
+        while self.clock.advance_to_next(target) {
+            self.process();
+        }
+    }
+
+    /// Passes the oldest captured command output to the component and processes it.
+    ///
+    /// Returns [`false`] if there was no command output to deliver.
+    pub fn deliver_command_output(&mut self) -> bool {
+        self.drop_aborted_command_outputs();
+        if let Some(output) = self.command_outputs.pop_front() {
+            self.command_handles.pop_front();
+            self.command_sender.emit(output);
+            self.process();
+            true
+        } else {
+            false
+        }
+    }
+
+    fn drop_aborted_command_outputs(&mut self) {
+        let handles = mem::take(&mut self.command_handles);
+        let outputs = mem::take(&mut self.command_outputs);
+        (self.command_handles, self.command_outputs) = handles
+            .into_iter()
+            .zip(outputs)
+            .filter(|(handle, _)| !handle.as_ref().is_some_and(CommandHandle::is_aborted))
+            .unzip();
+    }
+
+    /// Passes all currently captured command outputs to the component and processes them.
+    ///
+    /// Command outputs that are captured while processing will not be delivered.
+    pub fn deliver_command_outputs(&mut self) {
+        for _ in 0..self.command_outputs.len() {
+            self.deliver_command_output();
+        }
+    }
+
+    /// Drops the component, runs its shutdown and returns all remaining outputs.
+    #[must_use]
+    pub fn shutdown(self) -> Vec<C::Output> {
+        let Self {
+            controller,
+            output_receiver,
+            mut outputs,
+            ..
+        } = self;
+
+        drop(controller);
+
+        let context = glib::MainContext::ref_thread_default();
+        while context.iteration(false) {}
+
+        outputs.extend(output_receiver.0.try_iter());
+        outputs
+    }
+}
+
+impl<C: Component> Debug for ComponentTester<C> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ComponentTester")
+            .field("controller", &"<Controller>")
+            .field("outputs", &self.outputs)
+            .field("command_outputs", &self.command_outputs)
+            .finish()
+    }
+}
+
+/// A [`CommandExecutor`] that records all commands and only runs them on request.
+///
+/// Cloning this type returns a handle to the same executor,
+/// so one clone can be passed to a component while the test keeps another one.
+///
+/// ```
+/// use relm4::testing::{ComponentTester, ManualExecutor};
+/// use relm4::Component;
+/// # type MyComponent = ();
+///
+/// let executor = ManualExecutor::new();
+/// let builder = MyComponent::builder().command_executor(executor.clone());
+/// let mut tester = ComponentTester::from_builder(builder, ());
+///
+/// // Run all commands the component spawned during initialization.
+/// executor.run_all();
+/// tester.process();
+/// ```
+///
+/// # Note
+///
+/// Commands are run to completion on the calling thread, so commands
+/// that wait for their [`ShutdownReceiver`](crate::ShutdownReceiver) only finish
+/// once the component was shut down.
+/// To test how commands react to a shutdown, shut the component down first,
+/// for example with [`ComponentTester::shutdown()`], and run the commands afterwards.
+#[derive(Debug, Clone, Default)]
+pub struct ManualExecutor {
+    inner: Arc<Mutex<ManualExecutorInner>>,
+}
+
+#[derive(Default)]
+struct ManualExecutorInner {
+    pending: VecDeque<PendingCommand>,
+    spawned: Vec<&'static str>,
+}
+
+enum PendingCommand {
+    Async(&'static str, CommandFuture),
+    Blocking(&'static str, BlockingCommand),
+}
+
+impl PendingCommand {
+    const fn name(&self) -> &'static str {
+        match self {
+            Self::Async(name, _) | Self::Blocking(name, _) => name,
+        }
+    }
+}
+
+impl ManualExecutor {
+    /// Creates a new executor without any commands.
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Returns the names of all commands spawned so far,
+    /// including commands that already ran.
+    #[must_use]
+    pub fn spawned(&self) -> Vec<&'static str> {
+        self.inner.lock().unwrap().spawned.clone()
+    }
+
+    /// Returns the names of all commands that haven't run yet.
+    #[must_use]
+    pub fn pending(&self) -> Vec<&'static str> {
+        let inner = self.inner.lock().unwrap();
+        inner.pending.iter().map(PendingCommand::name).collect()
+    }
+
+    /// Runs the oldest pending command to completion on the current thread.
+    ///
+    /// Returns [`false`] if no command was pending.
+    ///
+    /// # Panics
+    ///
+    /// Panics if called from within an asynchronous execution context.
+    pub fn run_next(&self) -> bool {
+        // Don't hold the lock while running because commands might spawn new commands.
+        let command = self.inner.lock().unwrap().pending.pop_front();
+        match command {
+            Some(PendingCommand::Async(_, future)) => {
+                RUNTIME.block_on(future);
+                true
+            }
+            Some(PendingCommand::Blocking(_, func)) => {
+                let _guard = RUNTIME.enter();
+                func();
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Runs pending commands until no commands are left,
+    /// including commands that were spawned in the meantime.
+    pub fn run_all(&self) {
+        while self.run_next() {}
+    }
+
+    /// Drops all pending commands without running them,
+    /// just like commands are dropped on shutdown.
+    pub fn discard_pending(&self) {
+        // Drop the commands after releasing the lock.
+        let pending = mem::take(&mut self.inner.lock().unwrap().pending);
+        drop(pending);
+    }
+}
+
+impl CommandExecutor for ManualExecutor {
+    fn spawn(&self, name: &'static str, command: CommandFuture) {
+        let mut inner = self.inner.lock().unwrap();
+        inner.spawned.push(name);
+        inner
+            .pending
+            .push_back(PendingCommand::Async(name, command));
+    }
+
+    fn spawn_blocking(&self, name: &'static str, command: BlockingCommand) {
+        let mut inner = self.inner.lock().unwrap();
+        inner.spawned.push(name);
+        inner
+            .pending
+            .push_back(PendingCommand::Blocking(name, command));
+    }
+}
+
+impl Debug for ManualExecutorInner {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let pending: Vec<_> = self.pending.iter().map(PendingCommand::name).collect();
+        f.debug_struct("ManualExecutorInner")
+            .field("pending", &pending)
+            .field("spawned", &self.spawned)
+            .finish()
+    }
+}
+
+/// Helpers shared by the tests of several modules.
+#[cfg(test)]
+pub(crate) mod fixtures {
+    use std::cell::Cell;
+    use std::marker::PhantomData;
+
+    use gtk::glib;
+    use gtk::prelude::{GtkWindowExt, IsA, WidgetExt};
+
+    use crate::factory::{FactoryComponent, FactorySender};
+    use crate::{ComponentParts, ComponentSender, SimpleComponent};
+
+    /// Shows `widget` in a window and processes events until it's mapped.
+    pub(crate) fn show(widget: &impl IsA<gtk::Widget>) -> gtk::Window {
+        let window = gtk::Window::new();
+        window.set_child(Some(widget));
+        window.present();
+        let context = glib::MainContext::default();
+        while context.iteration(false) {}
+        assert!(widget.is_mapped());
+        window
+    }
+
+    /// Counts updates of the model and the view, as a component or as a factory element with index `I`.
+    pub(crate) struct ViewCounter<I = ()> {
+        pub(crate) value: u8,
+        pub(crate) views: Cell<u8>,
+        index: PhantomData<I>,
+    }
+
+    impl<I> Default for ViewCounter<I> {
+        fn default() -> Self {
+            Self {
+                value: 0,
+                views: Cell::default(),
+                index: PhantomData,
+            }
+        }
+    }
+
+    impl SimpleComponent for ViewCounter {
+        type Input = ();
+        type Output = ();
+        type Init = ();
+        type Root = gtk::Box;
+        type Widgets = ();
+
+        fn init_root() -> Self::Root {
+            gtk::Box::default()
+        }
+
+        fn init(
+            _init: Self::Init,
+            _root: Self::Root,
+            _sender: ComponentSender<Self>,
+        ) -> ComponentParts<Self> {
+            ComponentParts {
+                model: Self::default(),
+                widgets: (),
+            }
+        }
+
+        fn update(&mut self, _message: Self::Input, _sender: ComponentSender<Self>) {
+            self.value += 1;
+        }
+
+        fn update_view(&self, _widgets: &mut Self::Widgets, _sender: ComponentSender<Self>) {
+            self.views.set(self.views.get() + 1);
+        }
+    }
+
+    impl<I: 'static> FactoryComponent for ViewCounter<I> {
+        type ParentWidget = gtk::Box;
+        type CommandOutput = ();
+        type Input = ();
+        type Output = ();
+        type Init = ();
+        type Root = gtk::Label;
+        type Widgets = ();
+        type Index = I;
+
+        fn init_model(_init: Self::Init, _index: &I, _sender: FactorySender<Self>) -> Self {
+            Self::default()
+        }
+
+        fn init_root(&self) -> Self::Root {
+            gtk::Label::default()
+        }
+
+        fn init_widgets(
+            &mut self,
+            _index: &I,
+            _root: Self::Root,
+            _returned_widget: &gtk::Widget,
+            _sender: FactorySender<Self>,
+        ) -> Self::Widgets {
+        }
+
+        fn update(&mut self, _message: Self::Input, _sender: FactorySender<Self>) {
+            self.value += 1;
+        }
+
+        fn update_view(&self, _widgets: &mut Self::Widgets, _sender: FactorySender<Self>) {
+            self.views.set(self.views.get() + 1);
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{ComponentTester, ManualExecutor};
+    use crate::component::{ComponentMessage, MessageRecorder, RecordedMessage};
+    use crate::{Component, ComponentController, ComponentParts, ComponentSender};
+
+    struct Counter(u8);
+
+    #[derive(Debug, Clone)]
+    enum CounterMsg {
+        Increment,
+        Report,
+        Load,
+    }
+
+    impl Component for Counter {
+        type CommandOutput = u8;
+        type Input = CounterMsg;
+        type Output = u8;
+        type Init = u8;
+        type Root = ();
+        type Widgets = ();
+
+        fn init_root() -> Self::Root {}
+
+        fn init(
+            init: Self::Init,
+            _root: Self::Root,
+            _sender: ComponentSender<Self>,
+        ) -> ComponentParts<Self> {
+            ComponentParts {
+                model: Counter(init),
+                widgets: (),
+            }
+        }
+
+        fn update(&mut self, message: Self::Input, sender: ComponentSender<Self>, _: &()) {
+            match message {
+                CounterMsg::Increment => self.0 += 1,
+                CounterMsg::Report => sender.output(self.0).unwrap(),
+                CounterMsg::Load => {
+                    sender.oneshot_command(async { 10 });
+                }
+            }
+        }
+
+        fn update_cmd(&mut self, message: Self::CommandOutput, _: ComponentSender<Self>, _: &()) {
+            self.0 = message;
+        }
+    }
+
+    #[gtk::test]
+    fn tester_processes_inputs() {
+        let mut tester = ComponentTester::<Counter>::new(1);
+
+        tester.send(CounterMsg::Increment);
+        tester.send(CounterMsg::Increment);
+        assert_eq!(tester.model().0, 3);
+
+        tester.send(CounterMsg::Report);
+        assert_eq!(tester.take_outputs(), [3]);
+        assert!(tester.outputs().is_empty());
+    }
+
+    #[gtk::test]
+    fn tester_holds_back_commands() {
+        let mut tester = ComponentTester::<Counter>::new(0);
+
+        tester.send(CounterMsg::Load);
+        assert!(tester.command_outputs().is_empty());
+
+        tester.run_commands();
+        assert_eq!(tester.command_outputs(), &[10]);
+        assert_eq!(tester.model().0, 0);
+
+        assert!(tester.deliver_command_output());
+        assert_eq!(tester.model().0, 10);
+        assert!(!tester.deliver_command_output());
+    }
+
+    #[gtk::test]
+    fn manual_executor_runs_on_request() {
+        let executor = ManualExecutor::new();
+        let builder = Counter::builder().command_executor(executor.clone());
+        let mut tester = ComponentTester::from_builder(builder, 0);
+
+        tester.send(CounterMsg::Load);
+        tester.send(CounterMsg::Load);
+        assert_eq!(executor.pending().len(), 2);
+        assert!(tester.command_outputs().is_empty());
+
+        assert!(executor.run_next());
+        tester.process();
+        assert_eq!(executor.pending().len(), 1);
+        assert_eq!(executor.spawned().len(), 2);
+        assert_eq!(tester.command_outputs().len(), 1);
+
+        executor.discard_pending();
+        assert!(executor.pending().is_empty());
+        tester.deliver_command_outputs();
+        assert_eq!(tester.model().0, 10);
+    }
+
+    #[gtk::test]
+    fn replay_recorded_messages() {
+        let recorder = MessageRecorder::new();
+        let mut tester = ComponentTester::from_builder(Counter::builder().record(&recorder), 0);
+
+        tester.send(CounterMsg::Increment);
+        tester.send(CounterMsg::Load);
+        tester.run_commands();
+        tester.deliver_command_outputs();
+        tester.send(CounterMsg::Report);
+        assert_eq!(tester.take_outputs(), [10]);
+
+        let messages = recorder.take_messages();
+        assert_eq!(messages.len(), 5);
+        assert!(matches!(
+            messages[2].message,
+            ComponentMessage::CommandOutput(10)
+        ));
+
+        let mut replay = ComponentTester::replaying(Counter::builder(), 0);
+        for message in messages {
+            replay.replay(message);
+        }
+        assert_eq!(replay.model().0, 10);
+        assert_eq!(replay.take_outputs(), [10]);
+    }
+
+    #[gtk::test]
+    fn record_outputs_in_order() {
+        let recorder = MessageRecorder::new();
+        let mut tester = ComponentTester::from_builder(Counter::builder().record(&recorder), 0);
+
+        let sender = tester.controller().sender().clone();
+        sender.emit(CounterMsg::Report);
+        sender.emit(CounterMsg::Increment);
+        tester.process();
+
+        let messages = recorder.take_messages();
+        assert!(matches!(
+            messages.as_slice(),
+            [
+                RecordedMessage {
+                    message: ComponentMessage::Input(CounterMsg::Report),
+                    ..
+                },
+                RecordedMessage {
+                    message: ComponentMessage::Output(0),
+                    ..
+                },
+                RecordedMessage {
+                    message: ComponentMessage::Input(CounterMsg::Increment),
+                    ..
+                },
+            ]
+        ));
+    }
+}